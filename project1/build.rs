@@ -0,0 +1,20 @@
+// Check that the C API can be exported when it is built. The header is written to OUT_DIR,
+// the copy in include/ is refreshed by hand after changing src/ffi.rs, with the cbindgen CLI:
+//     cbindgen --config cbindgen.toml --crate word-search-solver --output include/word_search_solver.h
+fn main() {
+    #[cfg(feature = "ffi")]
+    {
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("cargo sets the crate dir");
+        let out_dir = std::env::var("OUT_DIR").expect("cargo sets the output dir");
+        let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+            .expect("cbindgen.toml is valid");
+        cbindgen::Builder::new()
+            .with_crate(&crate_dir)
+            .with_config(config)
+            .generate()
+            .expect("the C API can be exported")
+            .write_to_file(format!("{}/word_search_solver.h", out_dir));
+    }
+}
@@ -0,0 +1,145 @@
+#ifndef WORD_SEARCH_SOLVER_H
+#define WORD_SEARCH_SOLVER_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * A board, opaque to C
+ */
+typedef struct WsBoard WsBoard;
+
+/**
+ * The words found by `ws_solve`, opaque to C
+ */
+typedef struct WsResults WsResults;
+
+/**
+ * A trie of target words, opaque to C
+ */
+typedef struct WsTrie WsTrie;
+
+/**
+ * One word found in the board. `word` belongs to the results and is valid until they are freed
+ */
+typedef struct WsMatch {
+  const char *word;
+  size_t start_row;
+  size_t start_col;
+  size_t end_row;
+  size_t end_col;
+} WsMatch;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * The message of the last failure on this thread, NULL if nothing failed yet.
+ * It is valid until the next failure on the same thread
+ */
+const char *ws_last_error(void);
+
+/**
+ * Create a board from the `len` bytes of `buffer`, in the format of the board files:
+ * one row per line, spaces are ignored and letters are upper-cased.
+ * Returns NULL if the rows are not a rectangle of letters or it has more than 10000 cells
+ *
+ * # Safety
+ * `buffer` must point to `len` readable bytes
+ */
+struct WsBoard *ws_board_new(const char *buffer, size_t len);
+
+/**
+ * The number of rows of the board, 0 if it is NULL
+ *
+ * # Safety
+ * `board` must come from `ws_board_new` and not be freed
+ */
+size_t ws_board_rows(const struct WsBoard *board);
+
+/**
+ * The number of columns of the board, 0 if it is NULL
+ *
+ * # Safety
+ * `board` must come from `ws_board_new` and not be freed
+ */
+size_t ws_board_cols(const struct WsBoard *board);
+
+/**
+ * Free a board, NULL is ignored
+ *
+ * # Safety
+ * `board` must come from `ws_board_new` and not be freed yet
+ */
+void ws_board_free(struct WsBoard *board);
+
+/**
+ * Create a trie from the `len` bytes of `buffer`, words separated by whitespace like the
+ * word files. Words are upper-cased to match the boards. Returns NULL if the text is not UTF-8
+ *
+ * # Safety
+ * `buffer` must point to `len` readable bytes
+ */
+struct WsTrie *ws_trie_new(const char *buffer, size_t len);
+
+/**
+ * Add a NUL-terminated word to the trie. Returns false if either is NULL or the word is not UTF-8
+ *
+ * # Safety
+ * `trie` must come from `ws_trie_new` and `word` must be a NUL-terminated string
+ */
+bool ws_trie_insert(struct WsTrie *trie, const char *word);
+
+/**
+ * Free a trie, NULL is ignored
+ *
+ * # Safety
+ * `trie` must come from `ws_trie_new` and not be freed yet
+ */
+void ws_trie_free(struct WsTrie *trie);
+
+/**
+ * Find every word of the trie in the board, free the results with `ws_results_free`.
+ * Returns NULL if the board or the trie is NULL
+ *
+ * # Safety
+ * `board` and `trie` must come from `ws_board_new` and `ws_trie_new` and not be freed
+ */
+struct WsResults *ws_solve(const struct WsBoard *board, const struct WsTrie *trie);
+
+/**
+ * The number of words found, 0 if the results handle is NULL
+ *
+ * # Safety
+ * `results` must come from `ws_solve` and not be freed
+ */
+size_t ws_results_len(const struct WsResults *results);
+
+/**
+ * Fill `out` with the word at `index`, in the order the search found them.
+ * Returns false if either pointer is NULL or `index` is not below `ws_results_len`
+ *
+ * # Safety
+ * `results` must come from `ws_solve` and not be freed, `out` must be writable
+ */
+bool ws_results_get(const struct WsResults *results, size_t index, struct WsMatch *out);
+
+/**
+ * Free the results and their words, NULL is ignored
+ *
+ * # Safety
+ * `results` must come from `ws_solve` and not be freed yet
+ */
+void ws_results_free(struct WsResults *results);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* WORD_SEARCH_SOLVER_H */
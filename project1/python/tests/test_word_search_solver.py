@@ -0,0 +1,47 @@
+import pytest
+
+import word_search_solver as wss
+
+
+def test_board():
+    board = wss.Board(["d o g", "x a x"])
+    assert (board.rows, board.cols) == (2, 3)
+    assert board.grid() == ["DOG", "XAX"]
+    assert board.word((0, 2), (0, 0)) == "GOD"
+    with pytest.raises(ValueError):
+        board.word((0, 0), (1, 2))
+    with pytest.raises(ValueError):
+        wss.Board(["abc", "ab"])
+
+
+def test_trie():
+    trie = wss.Trie(["rust"])
+    trie.insert("python")
+    assert trie.search("python") and "rust" in trie
+    assert trie.starts_with("ru") and not trie.search("ru")
+
+
+def test_solve():
+    board = wss.Board(["DOG", "XAX"])
+    matches = wss.solve(board, wss.Trie(["god", "cat"]))
+    assert matches == [{"word": "GOD", "start": (0, 2), "end": (0, 0)}]
+    missing = wss.find_missing(board, ["god", "cat"])
+    assert [m["word"] for m in missing] == ["CAT"]
+    assert missing[0]["prefix"] == "" and missing[0]["start"] is None
+    assert wss.leftover_letters(board, wss.Trie(["god"])) == "XAX"
+
+
+def test_generate():
+    puzzle = wss.generate(["rust", "python"], rows=6, cols=6, seed=3)
+    board = puzzle["board"]
+    words = sorted(m["word"] for m in wss.solve(board, wss.Trie(["rust", "python"])))
+    assert words == ["PYTHON", "RUST"]
+    assert sorted(p["word"] for p in puzzle["placements"]) == words
+    with pytest.raises(wss.GenerateError):
+        wss.generate(["abcdef"], rows=2, cols=2)
+    assert issubclass(wss.GenerateError, ValueError)
+
+
+def test_load_missing_file():
+    with pytest.raises(OSError):
+        wss.Board.load("no/such/board.txt", "no/such/words.txt")
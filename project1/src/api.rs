@@ -0,0 +1,406 @@
+use crate::board::{Board, WordPosition};
+use crate::generator::{generate, GeneratorOptions};
+use crate::solver::{find_missing_words, solve};
+use crate::trie::Trie;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Largest request body accepted, in bytes
+pub const MAX_BODY_BYTES: usize = 64 * 1024;
+/// Largest grid accepted, in cells
+pub const MAX_GRID_CELLS: usize = 100 * 100;
+/// Most words accepted in one request
+pub const MAX_WORDS: usize = 1000;
+/// Largest side of a generated grid
+pub const MAX_GENERATED_SIZE: usize = 50;
+
+/// The grid is a list of rows of letters, spaces in a row are ignored
+#[derive(Debug, Deserialize)]
+pub struct SolveRequest {
+    pub grid: Vec<String>,
+    pub words: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GenerateRequest {
+    pub words: Vec<String>,
+    pub rows: Option<usize>,
+    pub cols: Option<usize>,
+    pub seed: Option<u64>,
+    pub message: Option<String>,
+}
+
+/// A word read in the grid, from its first to its last letter as (row, column)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Match {
+    pub word: String,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct SolveResponse {
+    pub matches: Vec<Match>,
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct GenerateResponse {
+    pub grid: Vec<String>,
+    pub placements: Vec<Match>,
+}
+
+/// A puzzle is valid when every word can be read exactly once
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct ValidateResponse {
+    pub valid: bool,
+    pub missing: Vec<String>,
+    pub repeated: Vec<String>, // Words that can be read in more than one place
+}
+
+/// An error sent back as `{"error": {"code": ..., "message": ...}}` with an HTTP status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    pub status: u16,
+    pub code: &'static str,
+    pub message: String,
+}
+impl ApiError {
+    pub fn new(status: u16, code: &'static str, message: impl Into<String>) -> Self {
+        ApiError {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+    pub fn to_json(&self) -> String {
+        serde_json::json!({ "error": { "code": self.code, "message": self.message } }).to_string()
+    }
+}
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}: {}", self.status, self.code, self.message)
+    }
+}
+impl std::error::Error for ApiError {}
+
+/// Status and JSON body of an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Answer a request without any network, the server only moves bytes around this.
+/// Routes are `POST /solve`, `POST /generate` and `POST /validate`
+/// # Example
+/// ```
+/// use word_search_solver::api::handle;
+/// let body = br#"{"grid": ["CAT", "XXX"], "words": ["cat", "dog"]}"#;
+/// let response = handle("POST", "/solve", body);
+/// assert_eq!(response.status, 200);
+/// assert_eq!(
+///     response.body,
+///     r#"{"matches":[{"word":"CAT","start":[0,0],"end":[0,2]}],"missing":["DOG"]}"#
+/// );
+/// assert_eq!(handle("GET", "/solve", b"").status, 405);
+/// ```
+pub fn handle(method: &str, path: &str, body: &[u8]) -> ApiResponse {
+    let result = match path {
+        "/solve" | "/generate" | "/validate" if method != "POST" => Err(ApiError::new(
+            405,
+            "method_not_allowed",
+            format!("{} only accepts POST", path),
+        )),
+        _ if body.len() > MAX_BODY_BYTES => Err(too_large()),
+        "/solve" => parse(body).and_then(|request| to_json(&solve_request(&request)?)),
+        "/generate" => parse(body).and_then(|request| to_json(&generate_request(&request)?)),
+        "/validate" => parse(body).and_then(|request| to_json(&validate_request(&request)?)),
+        _ => Err(ApiError::new(
+            404,
+            "not_found",
+            format!("no endpoint at {}", path),
+        )),
+    };
+    match result {
+        Ok(body) => ApiResponse { status: 200, body },
+        Err(error) => ApiResponse {
+            status: error.status,
+            body: error.to_json(),
+        },
+    }
+}
+
+/// The error for a body over `MAX_BODY_BYTES`
+pub fn too_large() -> ApiError {
+    ApiError::new(
+        413,
+        "too_large",
+        format!("the body must be at most {} bytes", MAX_BODY_BYTES),
+    )
+}
+
+/// Find every word of the request in its grid
+pub fn solve_request(request: &SolveRequest) -> Result<SolveResponse, ApiError> {
+    let board = parse_grid(&request.grid)?;
+    let words = parse_words(&request.words)?;
+    let found = solve(&board, &trie(&words));
+    let missing = find_missing_words(&board, &words, &found)
+        .into_iter()
+        .map(|missing_word| missing_word.word)
+        .collect();
+    Ok(SolveResponse {
+        matches: found
+            .iter()
+            .map(|word_position| to_match(&board, word_position))
+            .collect(),
+        missing,
+    })
+}
+
+/// Generate a grid holding the words of the request
+pub fn generate_request(request: &GenerateRequest) -> Result<GenerateResponse, ApiError> {
+    let words = parse_words(&request.words)?;
+    let defaults = GeneratorOptions::default();
+    let options = GeneratorOptions {
+        rows: request.rows.unwrap_or(defaults.rows),
+        cols: request.cols.unwrap_or(defaults.cols),
+        seed: request.seed.unwrap_or(defaults.seed),
+        message: request.message.clone(),
+        // Keep a request that cannot be placed from holding the server for long
+        attempts: 10,
+        ..defaults
+    };
+    let sizes = 1..=MAX_GENERATED_SIZE;
+    if !sizes.contains(&options.rows) || !sizes.contains(&options.cols) {
+        return Err(ApiError::new(
+            400,
+            "invalid_size",
+            format!("rows and cols must be between 1 and {}", MAX_GENERATED_SIZE),
+        ));
+    }
+    let puzzle = generate(&words, &options)
+        .map_err(|e| ApiError::new(422, "generate_failed", e.to_string()))?;
+    Ok(GenerateResponse {
+        grid: puzzle
+            .board
+            .letters
+            .iter()
+            .map(|row| row.iter().collect())
+            .collect(),
+        placements: puzzle
+            .placements
+            .iter()
+            .map(|word_position| to_match(&puzzle.board, word_position))
+            .collect(),
+    })
+}
+
+/// Check that every word of the request can be read exactly once in its grid
+pub fn validate_request(request: &SolveRequest) -> Result<ValidateResponse, ApiError> {
+    let board = parse_grid(&request.grid)?;
+    let words = parse_words(&request.words)?;
+    let found = solve(&board, &trie(&words));
+    let missing: Vec<String> = find_missing_words(&board, &words, &found)
+        .into_iter()
+        .map(|missing_word| missing_word.word)
+        .collect();
+    // A palindrome is read twice on the same cells, which is still a single place
+    let mut places: Vec<(String, Vec<(usize, usize)>)> = found
+        .iter()
+        .map(|word_position| {
+            let mut cells = word_position.cells();
+            cells.sort();
+            (board.get_word(word_position), cells)
+        })
+        .collect();
+    places.sort();
+    places.dedup();
+    let mut repeated: Vec<String> = places
+        .windows(2)
+        .filter(|pair| pair[0].0 == pair[1].0)
+        .map(|pair| pair[0].0.clone())
+        .collect();
+    repeated.dedup();
+    Ok(ValidateResponse {
+        valid: missing.is_empty() && repeated.is_empty(),
+        missing,
+        repeated,
+    })
+}
+
+fn parse<'a, T: Deserialize<'a>>(body: &'a [u8]) -> Result<T, ApiError> {
+    serde_json::from_slice(body).map_err(|e| ApiError::new(400, "invalid_json", e.to_string()))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, ApiError> {
+    serde_json::to_string(value).map_err(|e| ApiError::new(500, "internal", e.to_string()))
+}
+
+fn to_match(board: &Board, word_position: &WordPosition) -> Match {
+    Match {
+        word: board.get_word(word_position),
+        start: word_position.start(),
+        end: word_position.end(),
+    }
+}
+
+fn trie(words: &[String]) -> Trie {
+    Trie::from(&words.iter().map(String::as_str).collect())
+}
+
+// The upper case of a letter, or the letter itself when its upper case takes several
+// characters (ß), so that grid cells and words are folded the same way
+pub(crate) fn upper_letter(c: char) -> char {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) => upper,
+        _ => c,
+    }
+}
+
+// A word folded letter by letter like the grid cells
+pub(crate) fn upper_word(word: &str) -> String {
+    word.chars().map(upper_letter).collect()
+}
+
+// Upper-cased letters of a non-empty rectangular grid
+pub(crate) fn parse_grid(grid: &[String]) -> Result<Board, ApiError> {
+    let invalid = |message: String| ApiError::new(400, "invalid_grid", message);
+    let letters: Vec<Vec<char>> = grid
+        .iter()
+        .map(|row| {
+            row.chars()
+                .filter(|c| !c.is_whitespace())
+                .map(upper_letter)
+                .collect::<Vec<char>>()
+        })
+        .filter(|row| !row.is_empty())
+        .collect();
+    if letters.is_empty() {
+        return Err(invalid("the grid is empty".to_string()));
+    }
+    if letters.iter().any(|row| row.len() != letters[0].len()) {
+        return Err(invalid(
+            "every row of the grid must have the same number of letters".to_string(),
+        ));
+    }
+    if letters.len() * letters[0].len() > MAX_GRID_CELLS {
+        return Err(invalid(format!(
+            "the grid must have at most {} cells",
+            MAX_GRID_CELLS
+        )));
+    }
+    if let Some(c) = letters.iter().flatten().find(|c| !c.is_alphabetic()) {
+        return Err(invalid(format!("{:?} is not a letter", c)));
+    }
+    Ok(Board::new(&letters))
+}
+
+// Upper-cased words, empty ones are skipped
+pub(crate) fn parse_words(words: &[String]) -> Result<Vec<String>, ApiError> {
+    let invalid = |message: String| ApiError::new(400, "invalid_words", message);
+    if words.len() > MAX_WORDS {
+        return Err(invalid(format!("at most {} words are accepted", MAX_WORDS)));
+    }
+    let words: Vec<String> = words
+        .iter()
+        .map(|word| upper_word(word.trim()))
+        .filter(|word| !word.is_empty())
+        .collect();
+    if let Some(word) = words
+        .iter()
+        .find(|word| !word.chars().all(char::is_alphabetic))
+    {
+        return Err(invalid(format!("{:?} is not a word made of letters", word)));
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn error_code(response: &ApiResponse) -> String {
+        let json: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        json["error"]["code"].as_str().unwrap().to_string()
+    }
+    #[test]
+    fn test_errors() {
+        let response = handle("POST", "/solve", b"{\"grid\": [");
+        assert_eq!(
+            (response.status, error_code(&response)),
+            (400, "invalid_json".into())
+        );
+        let response = handle("POST", "/solve", br#"{"grid": ["AB", "C"], "words": []}"#);
+        assert_eq!(
+            (response.status, error_code(&response)),
+            (400, "invalid_grid".into())
+        );
+        let response = handle(
+            "POST",
+            "/validate",
+            br#"{"grid": ["AB"], "words": ["a-b"]}"#,
+        );
+        assert_eq!(
+            (response.status, error_code(&response)),
+            (400, "invalid_words".into())
+        );
+        let response = handle("POST", "/nowhere", b"{}");
+        assert_eq!(
+            (response.status, error_code(&response)),
+            (404, "not_found".into())
+        );
+        let response = handle("POST", "/solve", &vec![b' '; MAX_BODY_BYTES + 1]);
+        assert_eq!(
+            (response.status, error_code(&response)),
+            (413, "too_large".into())
+        );
+        let response = handle(
+            "POST",
+            "/generate",
+            br#"{"words": ["abcdef"], "rows": 2, "cols": 2}"#,
+        );
+        assert_eq!(
+            (response.status, error_code(&response)),
+            (422, "generate_failed".into())
+        );
+    }
+    #[test]
+    fn test_generate_then_validate() {
+        let response = handle(
+            "POST",
+            "/generate",
+            br#"{"words": ["rust", "trie", "board"], "rows": 6, "cols": 6, "seed": 1}"#,
+        );
+        assert_eq!(response.status, 200);
+        let json: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        let grid: Vec<String> = serde_json::from_value(json["grid"].clone()).unwrap();
+        assert_eq!(grid.len(), 6);
+        let request = SolveRequest {
+            grid,
+            words: vec!["rust".into(), "trie".into(), "board".into()],
+        };
+        let validation = validate_request(&request).unwrap();
+        assert!(validation.missing.is_empty());
+        let request = SolveRequest {
+            grid: vec!["ABA".into(), "XAB".into()],
+            words: vec!["aba".into(), "ab".into(), "zz".into()],
+        };
+        assert_eq!(
+            validate_request(&request).unwrap(),
+            ValidateResponse {
+                valid: false,
+                missing: vec!["ZZ".into()],
+                repeated: vec!["AB".into()],
+            }
+        );
+    }
+    #[test]
+    fn test_grid_and_words_fold_alike() {
+        let request = SolveRequest {
+            grid: vec!["été".into(), "ßxx".into()],
+            words: vec!["ÉTÉ".into(), "ß".into()],
+        };
+        assert!(solve_request(&request).unwrap().missing.is_empty());
+    }
+}
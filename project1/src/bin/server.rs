@@ -0,0 +1,32 @@
+use std::env;
+use word_search_solver::server::{bind, serve};
+
+const USAGE: &str = "Usage: word-search-server [--port PORT]
+
+Serve the solver as a JSON API on http://127.0.0.1:PORT (default 8080):
+    POST /solve      {\"grid\": [\"ROW\", ...], \"words\": [\"WORD\", ...]}
+    POST /validate   same body, checks every word can be read exactly once
+    POST /generate   {\"words\": [...], \"rows\": 15, \"cols\": 15, \"seed\": 0, \"message\": \"...\"}";
+
+fn main() {
+    let mut port = 8080;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next().map(|value| value.parse())) {
+            ("--port", Some(Ok(value))) => port = value,
+            _ => {
+                eprintln!("invalid option {}\n\n{}", arg, USAGE);
+                std::process::exit(2);
+            }
+        }
+    }
+    let server = match bind(port) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Could not listen on port {}: {}", port, e);
+            std::process::exit(1);
+        }
+    };
+    println!("Listening on http://127.0.0.1:{}", port);
+    serve(&server);
+}
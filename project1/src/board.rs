@@ -1,16 +1,66 @@
 use crate::state::search_state::SearchState;
 use crate::trie::Trie;
+#[cfg(feature = "gui")]
 use ggez::glam::Vec2;
+use serde::{Deserialize, Serialize};
 use std::slice::Iter;
-#[derive(Debug)]
-
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WordPosition {
     start: (usize, usize),
     end: (usize, usize),
+    step: (i32, i32), // Rows and columns between two letters, (0, 0) for a single letter
 }
 impl WordPosition {
+    /// A word read from `start` to `end` through every cell of the line between them
     pub fn new(start: (usize, usize), end: (usize, usize)) -> Self {
-        WordPosition { start, end }
+        let di = end.0 as i32 - start.0 as i32;
+        let dj = end.1 as i32 - start.1 as i32;
+        let steps = gcd(di.abs(), dj.abs());
+        let step = if steps == 0 {
+            (0, 0)
+        } else {
+            (di / steps, dj / steps)
+        };
+        WordPosition { start, end, step }
+    }
+    /// A word read from `start` for `distance` steps of `direction`, None if it leaves the first quadrant
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::{Direction, WordPosition};
+    /// let knight = WordPosition::along((0, 0), &Direction::Step(1, 2), 2).unwrap();
+    /// assert_eq!(knight.end(), (2, 4));
+    /// assert_eq!(knight.cells(), vec![(0, 0), (1, 2), (2, 4)]);
+    /// // The same ends read every cell of the line in between
+    /// assert_eq!(WordPosition::new((0, 0), (2, 4)).cells(), vec![(0, 0), (1, 2), (2, 4)]);
+    /// assert_eq!(WordPosition::new((0, 0), (0, 2)).cells().len(), 3);
+    /// assert_eq!(WordPosition::along((0, 0), &Direction::Step(0, 2), 1).unwrap().cells().len(), 2);
+    /// assert_eq!(WordPosition::along((0, 0), &Direction::Up, 1), None);
+    /// ```
+    pub fn along(start: (usize, usize), direction: &Direction, distance: i32) -> Option<Self> {
+        let end = Board::get_pos_from_direction(start.0, start.1, direction, distance)?;
+        let coord_diff = direction.to_coord_diff();
+        let step = (
+            coord_diff.0 * distance.signum(),
+            coord_diff.1 * distance.signum(),
+        );
+        Some(WordPosition { start, end, step })
+    }
+    pub fn start(&self) -> (usize, usize) {
+        self.start
+    }
+    pub fn end(&self) -> (usize, usize) {
+        self.end
+    }
+    pub fn step(&self) -> (i32, i32) {
+        self.step
+    }
+    /// The same cells read from the end to the start
+    pub fn reversed(&self) -> Self {
+        WordPosition {
+            start: self.end,
+            end: self.start,
+            step: (-self.step.0, -self.step.1),
+        }
     }
     /// Convert from 2D position to 1D position
     /// For example: an 5x5 board, the position (1,2) will be converted to 7, and (4,4) will be converted to 24(0-indexed)
@@ -33,7 +83,49 @@ impl WordPosition {
         let end = end_i * board_size + end_j;
         (start, end)
     }
+    /// Every cell covered by the word, from its start to its end
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::WordPosition;
+    /// let word_pos = WordPosition::new((2,0), (0,2));
+    /// assert_eq!(word_pos.cells(), vec![(2,0), (1,1), (0,2)]);
+    /// ```
+    pub fn cells(&self) -> Vec<(usize, usize)> {
+        let (di, dj) = self.step;
+        let length = if di != 0 {
+            (self.end.0 as i32 - self.start.0 as i32) / di
+        } else if dj != 0 {
+            (self.end.1 as i32 - self.start.1 as i32) / dj
+        } else {
+            0
+        };
+        (0..=length)
+            .map(|k| {
+                (
+                    (self.start.0 as i32 + di * k) as usize,
+                    (self.start.1 as i32 + dj * k) as usize,
+                )
+            })
+            .collect()
+    }
+    /// The segments to draw over the word: the whole line when its letters are next to each
+    /// other, otherwise a dot on each letter so that the skipped cells are not covered
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::{Direction, WordPosition};
+    /// let word_pos = WordPosition::new((0, 0), (2, 2));
+    /// assert_eq!(word_pos.strokes(), vec![((0, 0), (2, 2))]);
+    /// let word_pos = WordPosition::along((0, 0), &Direction::Step(0, 2), 1).unwrap();
+    /// assert_eq!(word_pos.strokes(), vec![((0, 0), (0, 0)), ((0, 2), (0, 2))]);
+    /// ```
+    pub fn strokes(&self) -> Vec<((usize, usize), (usize, usize))> {
+        if self.step.0.abs() <= 1 && self.step.1.abs() <= 1 {
+            return vec![(self.start, self.end)];
+        }
+        self.cells().into_iter().map(|cell| (cell, cell)).collect()
+    }
     /// Convert the raw usize position to a Vec2 tuple
+    #[cfg(feature = "gui")]
     pub fn to_vec2(&self) -> (Vec2, Vec2) {
         let start = Vec2::new(self.start.1 as f32, self.start.0 as f32);
         let end = Vec2::new(self.end.1 as f32, self.end.0 as f32);
@@ -45,17 +137,49 @@ pub struct Board {
     pub letters: Vec<Vec<char>>,
     cols: usize,
     rows: usize,
+    steps: Vec<Direction>, // Searched after the 8 directions
 }
 impl Board {
     pub fn new(letters: &Vec<Vec<char>>) -> Self {
-        let cols = letters.get(0).unwrap().len();
+        let cols = letters.first().unwrap().len();
         let rows = letters.len();
         Board {
             letters: letters.to_owned(),
             cols,
             rows,
+            steps: Vec::new(),
+        }
+    }
+    /// Also read words along these steps, like `Direction::Step(1, 2)` for knight moves.
+    /// The 8 directions, repeated steps and `Step(0, 0)` are left out
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::{Board, Direction};
+    /// let mut board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+    /// board.set_steps(vec![Direction::Step(2, 0), Direction::Step(0, 1), Direction::Step(2, 0)]);
+    /// assert_eq!(board.steps(), &[Direction::Step(2, 0)]);
+    /// assert_eq!(board.directions().count(), 9);
+    /// ```
+    pub fn set_steps(&mut self, steps: Vec<Direction>) {
+        self.steps.clear();
+        for step in steps {
+            let coord_diff = step.to_coord_diff();
+            if coord_diff.0 == 0 && coord_diff.1 == 0
+                || Direction::from_coord_diff(&coord_diff).is_some()
+                || self.steps.contains(&step)
+            {
+                continue;
+            }
+            self.steps.push(step);
         }
     }
+    pub fn steps(&self) -> &[Direction] {
+        &self.steps
+    }
+    /// Every direction the search reads words in, the 8 directions then the steps of the board
+    pub fn directions(&self) -> impl Iterator<Item = &Direction> {
+        Direction::iterator().chain(self.steps.iter())
+    }
     /// Given current position, return the next position in the board
     /// # Arguments
     /// * `i` - The row index of the position
@@ -124,7 +248,7 @@ impl Board {
             .is_none()
         {
             // If there are no more directions to check, move to the next position
-            if direction.next().is_none() {
+            if self.next_direction(&direction).is_none() {
                 self.next_pos(i, j)?;
                 return Some(SearchState::from(
                     self.next_pos(i, j).unwrap(),
@@ -135,7 +259,7 @@ impl Board {
             // Otherwise, move to the next direction
             return Some(SearchState::from(
                 state.position,
-                direction.next().unwrap(),
+                self.next_direction(&direction).unwrap(),
                 0,
             ));
         }
@@ -148,7 +272,7 @@ impl Board {
             ));
         }
         // If the current direction is not feasible, attempt to move to the next direction
-        if direction.next().is_none() {
+        if self.next_direction(&direction).is_none() {
             self.next_pos(i, j)?;
 
             // Otherwise, move to the next position
@@ -161,10 +285,20 @@ impl Board {
         // Otherwise, move to the next direction
         Some(SearchState::from(
             state.position,
-            direction.next().unwrap(),
+            self.next_direction(&direction).unwrap(),
             0,
         ))
     }
+    // The direction searched after this one, the steps of the board come after the 8 directions
+    fn next_direction(&self, direction: &Direction) -> Option<Direction> {
+        match direction {
+            Direction::Step(..) => {
+                let index = self.steps.iter().position(|step| step == direction)?;
+                self.steps.get(index + 1).copied()
+            }
+            _ => direction.next().or_else(|| self.steps.first().copied()),
+        }
+    }
     pub fn check_state(&self, state: &mut SearchState, trie: &Trie) -> Option<WordPosition> {
         let (i, j) = state.position;
         let distance = state.distance;
@@ -178,11 +312,7 @@ impl Board {
                 Some(_) => state.feasible = true,
             }
             if trie.search(&string) {
-                let word_position = WordPosition::new(
-                    (i, j),
-                    Board::get_pos_from_direction(i, j, &direction, distance).unwrap_or_default(),
-                );
-                return Some(word_position);
+                return WordPosition::along((i, j), &direction, distance);
             }
         }
         None
@@ -271,6 +401,142 @@ impl Board {
         }
         word
     }
+    /// Get the word covered by a word position
+    /// # Arguments
+    /// * `position` - The start and end of the word
+    /// # Returns
+    /// * `String` - The word
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::{Board, WordPosition};
+    /// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+    /// assert_eq!(board.get_word(&WordPosition::new((0, 0), (2, 2))), "aei");
+    /// assert_eq!(board.get_word(&WordPosition::new((2, 1), (0, 1))), "heb");
+    /// ```
+    pub fn get_word(&self, position: &WordPosition) -> String {
+        position
+            .cells()
+            .into_iter()
+            .map(|(i, j)| self.letters[i][j])
+            .collect()
+    }
+    /// Find the longest prefix of a word that can be read in the board in one of its directions
+    /// # Arguments
+    /// * `word` - The word to look for
+    /// # Returns
+    /// * `Option<(String, WordPosition)>` - The longest matched prefix and where it was, None if not even the first letter is in the board
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::{Board, WordPosition};
+    /// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+    /// assert_eq!(board.longest_prefix("aex"), Some(("ae".to_string(), WordPosition::new((0, 0), (1, 1)))));
+    /// assert_eq!(board.longest_prefix("fcz"), Some(("fc".to_string(), WordPosition::new((1, 2), (0, 2)))));
+    /// assert_eq!(board.longest_prefix("xyz"), None);
+    /// ```
+    pub fn longest_prefix(&self, word: &str) -> Option<(String, WordPosition)> {
+        let chars: Vec<char> = word.chars().collect();
+        let mut best: Option<(usize, WordPosition)> = None;
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                for direction in self.directions() {
+                    let mut length = 0;
+                    while length < chars.len() {
+                        match Board::get_pos_from_direction(i, j, direction, length as i32) {
+                            Some((x, y)) if self.get_letter(Some(x), Some(y)).is_some() => {
+                                if self.letters[x][y] != chars[length] {
+                                    break;
+                                }
+                            }
+                            _ => break,
+                        }
+                        length += 1;
+                    }
+                    if length > 0 && best.as_ref().is_none_or(|(l, _)| length > *l) {
+                        let position = WordPosition::along((i, j), direction, length as i32 - 1)
+                            .expect("the prefix is inside the board");
+                        best = Some((length, position));
+                    }
+                }
+            }
+        }
+        best.map(|(length, position)| (chars[..length].iter().collect(), position))
+    }
+    /// Snap a selection between two cells onto the closest of the 8 directions, clamped to the board.
+    /// A selection that ends off these lines, on a letter reached by a step of the board, follows that step
+    /// # Arguments
+    /// * `start` - The cell where the selection started
+    /// * `end` - The cell where the selection currently ends
+    /// # Returns
+    /// * `Option<WordPosition>` - The snapped selection, None if start is outside the board
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::{Board, WordPosition};
+    /// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+    /// assert_eq!(board.snap_selection((0, 0), (0, 2)), Some(WordPosition::new((0, 0), (0, 2))));
+    /// assert_eq!(board.snap_selection((0, 0), (2, 1)), Some(WordPosition::new((0, 0), (2, 2))));
+    /// assert_eq!(board.snap_selection((2, 0), (0, 1)), Some(WordPosition::new((2, 0), (0, 2))));
+    /// assert_eq!(board.snap_selection((1, 1), (1, 1)), Some(WordPosition::new((1, 1), (1, 1))));
+    /// assert_eq!(board.snap_selection((3, 0), (0, 0)), None);
+    /// ```
+    pub fn snap_selection(
+        &self,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> Option<WordPosition> {
+        self.get_letter(Some(start.0), Some(start.1))?;
+        let di = end.0 as f32 - start.0 as f32;
+        let dj = end.1 as f32 - start.1 as f32;
+        if di == 0.0 && dj == 0.0 {
+            return Some(WordPosition::new(start, start));
+        }
+        if di != 0.0 && dj != 0.0 && di.abs() != dj.abs() {
+            if let Some(position) = self.snap_to_step(start, end) {
+                return Some(position);
+            }
+        }
+        // Round the angle of the selection to the nearest multiple of 45 degrees
+        let octant = (di.atan2(dj) / std::f32::consts::FRAC_PI_4).round() as i32;
+        let coord_diff = match octant.rem_euclid(8) {
+            0 => CoordDiff(0, 1),
+            1 => CoordDiff(1, 1),
+            2 => CoordDiff(1, 0),
+            3 => CoordDiff(1, -1),
+            4 => CoordDiff(0, -1),
+            5 => CoordDiff(-1, -1),
+            6 => CoordDiff(-1, 0),
+            _ => CoordDiff(-1, 1),
+        };
+        let direction = Direction::from_coord_diff(&coord_diff)?;
+        let mut distance = di.abs().max(dj.abs()) as i32;
+        while distance > 0 {
+            if let Some((x, y)) =
+                Board::get_pos_from_direction(start.0, start.1, &direction, distance)
+            {
+                if self.get_letter(Some(x), Some(y)).is_some() {
+                    return Some(WordPosition::new(start, (x, y)));
+                }
+            }
+            distance -= 1;
+        }
+        Some(WordPosition::new(start, start))
+    }
+    // The word from `start` to `end` along one of the steps of the board, if `end` is a letter it reaches
+    fn snap_to_step(&self, start: (usize, usize), end: (usize, usize)) -> Option<WordPosition> {
+        self.get_letter(Some(end.0), Some(end.1))?;
+        let di = end.0 as i32 - start.0 as i32;
+        let dj = end.1 as i32 - start.1 as i32;
+        self.steps.iter().find_map(|step| {
+            let coord_diff = step.to_coord_diff();
+            let distance = if coord_diff.0 != 0 {
+                di / coord_diff.0
+            } else {
+                dj / coord_diff.1
+            };
+            (distance > 0 && coord_diff.0 * distance == di && coord_diff.1 * distance == dj)
+                .then(|| WordPosition::along(start, step, distance))
+                .flatten()
+        })
+    }
     pub fn get_rows(&self) -> usize {
         self.rows
     }
@@ -298,8 +564,6 @@ impl Board {
     /// assert_eq!(board.get_letter(None, None), None);
     /// assert_eq!(board.get_letter(Some(3), Some(0)), None);
     /// ```
-    ///
-
     pub fn get_letter(&self, x: Option<usize>, y: Option<usize>) -> Option<String> {
         let x = x?;
         let y = y?;
@@ -389,7 +653,7 @@ impl Board {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum Direction {
     #[default]
     Up,
@@ -400,6 +664,7 @@ pub enum Direction {
     UpLeft,
     DownRight,
     DownLeft,
+    Step(i32, i32), // Any other step, in rows then columns, like (1, 2) for a knight move
 }
 
 pub struct CoordDiff(pub i32, pub i32);
@@ -415,8 +680,25 @@ impl Direction {
             Direction::UpLeft => CoordDiff(-1, -1),
             Direction::DownRight => CoordDiff(1, 1),
             Direction::DownLeft => CoordDiff(1, -1),
+            Direction::Step(di, dj) => CoordDiff(*di, *dj),
         }
     }
+    /// Get the direction of a unit step, None if the step is not one of the 8 directions
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::{CoordDiff, Direction};
+    /// assert_eq!(Direction::from_coord_diff(&CoordDiff(-1, 1)), Some(Direction::UpRight));
+    /// assert_eq!(Direction::from_coord_diff(&CoordDiff(2, 0)), None);
+    /// ```
+    pub fn from_coord_diff(coord_diff: &CoordDiff) -> Option<Direction> {
+        Direction::iterator()
+            .find(|direction| {
+                let diff = direction.to_coord_diff();
+                diff.0 == coord_diff.0 && diff.1 == coord_diff.1
+            })
+            .copied()
+    }
+    /// The 8 directions, in the order the search tries them
     pub fn iterator() -> Iter<'static, Direction> {
         static DIRECTIONS: [Direction; 8] = [
             Direction::Up,
@@ -440,9 +722,18 @@ impl Direction {
             Direction::UpLeft => Some(Direction::DownLeft),
             Direction::DownLeft => Some(Direction::DownRight),
             Direction::DownRight => None,
+            Direction::Step(..) => None,
         }
     }
 }
+// Greatest common divisor, 0 when both are 0
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
 #[cfg(test)]
 mod tests {
 
@@ -486,4 +777,27 @@ mod tests {
         assert_eq!(Board::add(0, -1), None);
         assert_eq!(Board::add(2, -1), Some(1));
     }
+    #[test]
+    fn test_search_along_steps() {
+        let mut b = Board::new(&vec![
+            vec!['k', 'a', 'b', 'c', 'd'],
+            vec!['e', 'f', 'n', 'g', 'h'],
+            vec!['s', 'm', 'k', 'o', 'i'],
+        ]);
+        let trie = Trie::from(&vec!["kni", "ski"]);
+        assert!(crate::solver::solve(&b, &trie).is_empty());
+        b.set_steps(vec![
+            Direction::Step(1, 2),
+            Direction::Step(0, 2),
+            Direction::Step(1, 0),
+        ]);
+        assert_eq!(b.steps(), &[Direction::Step(1, 2), Direction::Step(0, 2)]);
+        let found = crate::solver::solve(&b, &trie);
+        let words: Vec<String> = found.iter().map(|pos| b.get_word(pos)).collect();
+        assert_eq!(words, vec!["kni", "ski"]);
+        assert_eq!(found[0].cells(), vec![(0, 0), (1, 2), (2, 4)]);
+        assert_eq!(found[1].step(), (0, 2));
+        // Dragging from K to I does not follow any of the 8 directions
+        assert_eq!(b.snap_selection((0, 0), (2, 4)), Some(found[0].clone()));
+    }
 }
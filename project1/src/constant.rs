@@ -1,6 +1,3 @@
-pub const START_X: f32 = 400.0;
-pub const GRID_SIZE: f32 = 50.0;
-pub const START_Y: f32 = 100.0;
 pub const BOARD_SIZE: usize = 15; // number of cell of the grid
 pub const SCREEN_WIDTH: f32 = 1600.0;
 pub const SCREEN_HEIGHT: f32 = 900.0;
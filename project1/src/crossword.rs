@@ -0,0 +1,139 @@
+use crate::board::{Board, Direction, WordPosition};
+use crate::trie::Trie;
+use std::fmt;
+
+/// The character of a black square in crossword grids, unless another one is given
+pub const BLACK_SQUARE: char = '#';
+
+/// A crossword entry, a run of two or more letters between black squares or the edges of the grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub word: String,
+    pub position: WordPosition,
+    /// `Direction::Right` for across entries, `Direction::Down` for down entries
+    pub direction: Direction,
+}
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let direction = match self.direction {
+            Direction::Right => "across",
+            _ => "down",
+        };
+        write!(
+            f,
+            "{} {} from {:?} to {:?}",
+            self.word,
+            direction,
+            self.position.start(),
+            self.position.end()
+        )
+    }
+}
+
+/// Every across entry then every down entry of a crossword grid, each in reading order
+/// # Example
+/// ```
+/// use word_search_solver::board::{Board, Direction};
+/// use word_search_solver::crossword::{entries, BLACK_SQUARE};
+/// let board = Board::new(&vec![
+///     vec!['C', 'A', 'T'],
+///     vec!['#', 'X', '#'],
+///     vec!['D', 'E', 'W'],
+/// ]);
+/// let words: Vec<String> = entries(&board, BLACK_SQUARE).into_iter().map(|entry| entry.word).collect();
+/// // Single letters between black squares are not entries
+/// assert_eq!(words, vec!["CAT", "DEW", "AXE"]);
+/// ```
+pub fn entries(board: &Board, black_square: char) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    for direction in [Direction::Right, Direction::Down] {
+        for i in 0..board.get_rows() {
+            for j in 0..board.get_cols() {
+                let is_letter = |distance: i32| {
+                    Board::get_pos_from_direction(i, j, &direction, distance)
+                        .and_then(|(x, y)| board.letters.get(x)?.get(y))
+                        .is_some_and(|&letter| letter != black_square)
+                };
+                // An entry starts on a letter that does not follow another one
+                if !is_letter(0) || is_letter(-1) {
+                    continue;
+                }
+                let mut length = 1;
+                while is_letter(length) {
+                    length += 1;
+                }
+                if length < 2 {
+                    continue;
+                }
+                let word = board
+                    .get_string_from_direction(i, j, &direction, length - 1)
+                    .expect("the entry is inside the board");
+                let end = Board::get_pos_from_direction(i, j, &direction, length - 1)
+                    .expect("the entry is inside the board");
+                entries.push(Entry {
+                    word,
+                    position: WordPosition::new((i, j), end),
+                    direction,
+                });
+            }
+        }
+    }
+    entries
+}
+
+/// The entries of a filled crossword grid that are not words of the dictionary
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::crossword::{check_fill, BLACK_SQUARE};
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![vec!['C', 'A', 'T'], vec!['#', 'X', '#'], vec!['D', 'E', 'W']]);
+/// let dictionary = Trie::from(&vec!["CAT", "AXE", "DEW"]);
+/// assert!(check_fill(&board, &dictionary, BLACK_SQUARE).is_empty());
+/// let invalid = check_fill(&board, &Trie::from(&vec!["CAT", "AXE"]), BLACK_SQUARE);
+/// assert_eq!(invalid[0].to_string(), "DEW across from (2, 0) to (2, 2)");
+/// ```
+pub fn check_fill(board: &Board, dictionary: &Trie, black_square: char) -> Vec<Entry> {
+    entries(board, black_square)
+        .into_iter()
+        .filter(|entry| !dictionary.search(&entry.word))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::grid;
+    #[test]
+    fn test_entries_positions() {
+        let board = grid(&["AB#", "CDE", "#FG"]);
+        let entries = entries(&board, BLACK_SQUARE);
+        let found: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.word.as_str(), entry.position.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("AB", WordPosition::new((0, 0), (0, 1))),
+                ("CDE", WordPosition::new((1, 0), (1, 2))),
+                ("FG", WordPosition::new((2, 1), (2, 2))),
+                ("AC", WordPosition::new((0, 0), (1, 0))),
+                ("BDF", WordPosition::new((0, 1), (2, 1))),
+                ("EG", WordPosition::new((1, 2), (2, 2))),
+            ]
+        );
+        assert!(entries[..3]
+            .iter()
+            .all(|entry| entry.direction == Direction::Right));
+    }
+    #[test]
+    fn test_other_black_square() {
+        let board = grid(&["AB.C", "....", "DEFG"]);
+        let dictionary = Trie::from(&vec!["AB", "DEFG"]);
+        assert!(check_fill(&board, &dictionary, '.').is_empty());
+        // With the default marker the dots are letters, every row and column is an entry
+        // and only DEFG is a word
+        assert_eq!(check_fill(&board, &dictionary, BLACK_SQUARE).len(), 6);
+    }
+}
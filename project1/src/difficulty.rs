@@ -0,0 +1,230 @@
+use crate::board::{Board, Direction, WordPosition};
+use crate::heatmap::Heatmap;
+use crate::solver::solve;
+use crate::trie::Trie;
+use std::fmt;
+
+/// One thing that makes a puzzle harder, measured on the words found in the board
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifficultyFactor {
+    pub name: &'static str,
+    pub value: f32,  // The raw measure, in the unit given by `describe`
+    pub score: f32,  // How hard the measure makes the puzzle, between 0 and 1
+    pub weight: f32, // Share of the total score, the weights add up to 1
+    pub describe: &'static str,
+}
+
+/// Difficulty of a puzzle between 0 (trivial) and 100, with the factors it combines
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difficulty {
+    pub score: f32,
+    pub factors: Vec<DifficultyFactor>,
+    pub words_found: usize, // Target words missing from the board are not graded
+}
+impl Difficulty {
+    /// The factor with the given name
+    pub fn factor(&self, name: &str) -> Option<&DifficultyFactor> {
+        self.factors.iter().find(|factor| factor.name == name)
+    }
+}
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Difficulty: {:.1}/100 ({} words)",
+            self.score, self.words_found
+        )?;
+        for factor in &self.factors {
+            write!(
+                f,
+                "\n  {:<14} {:>5.1}/100 x {:.2}  ({:.2} {})",
+                factor.name,
+                factor.score * 100.0,
+                factor.weight,
+                factor.value,
+                factor.describe
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Grade a puzzle from the words of `target_words` that can be found in the board.
+/// The factors are:
+/// * direction mix - words read backward or along a diagonal are harder to spot
+/// * word length - short words stand out less than long ones
+/// * overlap - letters shared by several words
+/// * decoys - lines that start like a target word but do not end as one, found with `Trie::starts_with`
+/// * filler - cells that belong to no word and hide the others
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::difficulty::score_difficulty;
+/// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['x', 'x', 'x'], vec!['a', 'b', 'x']]);
+/// let difficulty = score_difficulty(&board, &["abc".to_string()]);
+/// // "ab" on the last row starts like "abc" but stops there
+/// assert_eq!(difficulty.factor("decoys").unwrap().value, 2.0 / 3.0);
+/// assert!(difficulty.score > 0.0 && difficulty.score < 100.0);
+/// ```
+pub fn score_difficulty(board: &Board, target_words: &[String]) -> Difficulty {
+    let target_words_str = target_words.iter().map(String::as_str).collect();
+    let trie = Trie::from(&target_words_str);
+    let found = solve(board, &trie);
+    let words = found.len().max(1) as f32;
+    let cells = (board.get_rows() * board.get_cols()).max(1) as f32;
+
+    let direction_mix = found.iter().map(direction_score).sum::<f32>() / words;
+
+    let letters: usize = found.iter().map(|word| word.cells().len()).sum();
+    let average_length = letters as f32 / words;
+
+    let heatmap = Heatmap::from_words(board.get_rows(), board.get_cols(), &found);
+    let (mut used, mut shared) = (0, 0);
+    for i in 0..board.get_rows() {
+        for j in 0..board.get_cols() {
+            match heatmap.get(i, j) {
+                0 => (),
+                1 => used += 1,
+                _ => {
+                    used += 1;
+                    shared += 1;
+                }
+            }
+        }
+    }
+    let overlap = shared as f32 / used.max(1) as f32;
+
+    let decoys = decoy_letters(board, &trie) as f32 / letters.max(1) as f32;
+
+    let filler = 1.0 - used as f32 / cells;
+
+    let factors = vec![
+        DifficultyFactor {
+            name: "direction mix",
+            value: direction_mix,
+            score: direction_mix,
+            weight: 0.3,
+            describe: "reversed and diagonal share",
+        },
+        DifficultyFactor {
+            name: "word length",
+            value: average_length,
+            // Words of 3 letters or less are the hardest, 10 letters or more the easiest
+            score: ((10.0 - average_length) / 7.0).clamp(0.0, 1.0),
+            weight: 0.15,
+            describe: "letters per word",
+        },
+        DifficultyFactor {
+            name: "overlap",
+            value: overlap,
+            score: overlap,
+            weight: 0.15,
+            describe: "used cells shared by words",
+        },
+        DifficultyFactor {
+            name: "decoys",
+            value: decoys,
+            // As many decoy letters as word letters is already half of the worst case
+            score: decoys / (1.0 + decoys),
+            weight: 0.25,
+            describe: "decoy letters per word letter",
+        },
+        DifficultyFactor {
+            name: "filler",
+            value: filler,
+            score: filler,
+            weight: 0.15,
+            describe: "cells in no word",
+        },
+    ];
+    let score = factors
+        .iter()
+        .map(|factor| factor.score * factor.weight)
+        .sum::<f32>()
+        * 100.0;
+    Difficulty {
+        score,
+        factors,
+        words_found: found.len(),
+    }
+}
+
+// 0 for a word read left to right or top to bottom, 0.5 if it is reversed or diagonal, 1 if both
+fn direction_score(word_position: &WordPosition) -> f32 {
+    let (start, end) = (word_position.start(), word_position.end());
+    let reversed = end.0 < start.0 || end.1 < start.1;
+    let diagonal = end.0 != start.0 && end.1 != start.1;
+    (reversed as u8 + diagonal as u8) as f32 / 2.0
+}
+
+// Letters of the lines that start like a target word, at least 2 letters long, without
+// containing one. Each cell and direction counts the longest such line once
+fn decoy_letters(board: &Board, trie: &Trie) -> usize {
+    let mut count = 0;
+    for i in 0..board.get_rows() {
+        for j in 0..board.get_cols() {
+            for direction in board.directions() {
+                count += decoy_length(board, trie, i, j, direction);
+            }
+        }
+    }
+    count
+}
+
+fn decoy_length(board: &Board, trie: &Trie, i: usize, j: usize, direction: &Direction) -> usize {
+    let mut length = 0;
+    let mut distance = 0;
+    while let Some(line) = board.get_string_from_direction(i, j, direction, distance) {
+        if !trie.starts_with(&line) {
+            break;
+        }
+        if trie.search(&line) {
+            return 0;
+        }
+        length = distance as usize + 1;
+        distance += 1;
+    }
+    if length < 2 {
+        return 0;
+    }
+    length
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::words;
+    #[test]
+    fn test_reversed_diagonal_words_are_harder() {
+        let easy = Board::new(&vec![
+            vec!['c', 'a', 't'],
+            vec!['d', 'o', 'g'],
+            vec!['x', 'x', 'x'],
+        ]);
+        let hard = Board::new(&vec![
+            vec!['x', 'x', 'd'],
+            vec!['x', 'o', 'a'],
+            vec!['g', 'x', 't'],
+        ]);
+        let (easy, hard) = (
+            score_difficulty(&easy, &words(&["cat", "dog"])),
+            score_difficulty(&hard, &words(&["dog", "tad"])),
+        );
+        assert_eq!(easy.factor("direction mix").unwrap().value, 0.0);
+        assert_eq!(hard.factor("direction mix").unwrap().value, 0.75);
+        assert_eq!(easy.factor("overlap").unwrap().value, 0.0);
+        assert_eq!(hard.factor("overlap").unwrap().value, 0.2);
+        assert!(hard.score > easy.score);
+        let weights: f32 = hard.factors.iter().map(|factor| factor.weight).sum();
+        assert!((weights - 1.0).abs() < 1e-6);
+    }
+    #[test]
+    fn test_missing_words_are_not_graded() {
+        let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+        let difficulty = score_difficulty(&board, &words(&["ab", "xyz"]));
+        assert_eq!(difficulty.words_found, 1);
+        assert_eq!(difficulty.factor("word length").unwrap().value, 2.0);
+        assert_eq!(difficulty.factor("filler").unwrap().value, 0.5);
+        assert!(difficulty.to_string().starts_with("Difficulty: "));
+    }
+}
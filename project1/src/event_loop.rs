@@ -0,0 +1,224 @@
+use crate::state::main_state::MainState;
+use ggez::event::winit_event::{ElementState, Event, KeyboardInput, MouseScrollDelta, WindowEvent};
+use ggez::event::{self, ControlFlow, ErrorOrigin, EventHandler, EventLoop};
+use ggez::input::gamepad::gilrs;
+use ggez::input::keyboard::{KeyInput, KeyMods};
+use ggez::winit::dpi;
+use ggez::{Context, GameResult};
+
+/// Run the visualizer, like `ggez::event::run` but files dropped on the window are
+/// forwarded to `MainState::file_dropped_event`, which ggez's event handler has no hook for.
+/// Every other event reaches the same handler as in ggez's loop, and errors go to `on_error`
+pub fn run(mut ctx: Context, event_loop: EventLoop<()>, mut state: MainState) -> ! {
+    event_loop.run(move |mut event, _, control_flow| {
+        let ctx = &mut ctx;
+        let state = &mut state;
+
+        if ctx.quit_requested {
+            let res = state.quit_event(ctx);
+            ctx.quit_requested = false;
+            if let Ok(false) = res {
+                ctx.continuing = false;
+            } else if catch_error(ctx, res, state, control_flow, ErrorOrigin::QuitEvent) {
+                return;
+            }
+        }
+        if !ctx.continuing {
+            *control_flow = ControlFlow::Exit;
+            return;
+        }
+        *control_flow = ControlFlow::Poll;
+
+        event::process_event(ctx, &mut event);
+        let (res, origin) = match event {
+            Event::WindowEvent { event, .. } => match event {
+                // Loading errors are reported by the handler itself, the window stays open
+                WindowEvent::DroppedFile(path) => {
+                    (state.file_dropped_event(ctx, path), ErrorOrigin::Update)
+                }
+                WindowEvent::Resized(size) => (
+                    state.resize_event(ctx, size.width as f32, size.height as f32),
+                    ErrorOrigin::ResizeEvent,
+                ),
+                WindowEvent::CloseRequested => {
+                    let res = state.quit_event(ctx);
+                    if let Ok(false) = res {
+                        ctx.continuing = false;
+                    }
+                    (res.map(|_| ()), ErrorOrigin::QuitEvent)
+                }
+                WindowEvent::Focused(gained) => {
+                    (state.focus_event(ctx, gained), ErrorOrigin::FocusEvent)
+                }
+                WindowEvent::ReceivedCharacter(ch) => {
+                    (state.text_input_event(ctx, ch), ErrorOrigin::TextInputEvent)
+                }
+                WindowEvent::ModifiersChanged(mods) => {
+                    ctx.keyboard.set_modifiers(KeyMods::from(mods));
+                    (Ok(()), ErrorOrigin::Update)
+                }
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: key_state,
+                            virtual_keycode: keycode,
+                            scancode,
+                            ..
+                        },
+                    ..
+                } => {
+                    let input = KeyInput {
+                        scancode,
+                        keycode,
+                        mods: ctx.keyboard.active_mods(),
+                    };
+                    match key_state {
+                        ElementState::Pressed => {
+                            let repeated = ctx.keyboard.is_key_repeated();
+                            (
+                                state.key_down_event(ctx, input, repeated),
+                                ErrorOrigin::KeyDownEvent,
+                            )
+                        }
+                        ElementState::Released => {
+                            (state.key_up_event(ctx, input), ErrorOrigin::KeyUpEvent)
+                        }
+                    }
+                }
+                WindowEvent::MouseWheel { delta, .. } => {
+                    let (x, y) = match delta {
+                        MouseScrollDelta::LineDelta(x, y) => (x, y),
+                        MouseScrollDelta::PixelDelta(pos) => {
+                            let scale_factor = ctx.gfx.window().scale_factor();
+                            let dpi::LogicalPosition { x, y } = pos.to_logical::<f32>(scale_factor);
+                            (x, y)
+                        }
+                    };
+                    (
+                        state.mouse_wheel_event(ctx, x, y),
+                        ErrorOrigin::MouseWheelEvent,
+                    )
+                }
+                WindowEvent::MouseInput {
+                    state: button_state,
+                    button,
+                    ..
+                } => {
+                    let position = ctx.mouse.position();
+                    match button_state {
+                        ElementState::Pressed => (
+                            state.mouse_button_down_event(ctx, button, position.x, position.y),
+                            ErrorOrigin::MouseButtonDownEvent,
+                        ),
+                        ElementState::Released => (
+                            state.mouse_button_up_event(ctx, button, position.x, position.y),
+                            ErrorOrigin::MouseButtonUpEvent,
+                        ),
+                    }
+                }
+                WindowEvent::CursorMoved { .. } => {
+                    let position = ctx.mouse.position();
+                    let delta = ctx.mouse.last_delta();
+                    (
+                        state.mouse_motion_event(ctx, position.x, position.y, delta.x, delta.y),
+                        ErrorOrigin::MouseMotionEvent,
+                    )
+                }
+                WindowEvent::Touch(touch) => (
+                    state.touch_event(ctx, touch.phase, touch.location.x, touch.location.y),
+                    ErrorOrigin::TouchEvent,
+                ),
+                WindowEvent::CursorEntered { .. } => (
+                    state.mouse_enter_or_leave(ctx, true),
+                    ErrorOrigin::MouseEnterOrLeave,
+                ),
+                WindowEvent::CursorLeft { .. } => (
+                    state.mouse_enter_or_leave(ctx, false),
+                    ErrorOrigin::MouseEnterOrLeave,
+                ),
+                _ => (Ok(()), ErrorOrigin::Update),
+            },
+            Event::MainEventsCleared => {
+                ctx.time.tick();
+                if gamepad_events(ctx, state, control_flow) {
+                    return;
+                }
+                let res = state.update(ctx);
+                if catch_error(ctx, res, state, control_flow, ErrorOrigin::Update) {
+                    return;
+                }
+                (draw_frame(ctx, state), ErrorOrigin::Draw)
+            }
+            _ => (Ok(()), ErrorOrigin::Update),
+        };
+        catch_error(ctx, res, state, control_flow, origin);
+    })
+}
+
+// Forward the pending gamepad events, true if one of them ended the loop
+fn gamepad_events(
+    ctx: &mut Context,
+    state: &mut MainState,
+    control_flow: &mut ControlFlow,
+) -> bool {
+    while let Some(gilrs::Event { id, event, .. }) = ctx.gamepad.next_event() {
+        // ggez only hands out the ids of connected gamepads
+        let Some(id) = ctx
+            .gamepad
+            .gamepads()
+            .find(|(_, gamepad)| gamepad.id() == id)
+            .map(|(id, _)| id)
+        else {
+            continue;
+        };
+        let (res, origin) = match event {
+            gilrs::EventType::ButtonPressed(button, _) => (
+                state.gamepad_button_down_event(ctx, button, id),
+                ErrorOrigin::GamepadButtonDownEvent,
+            ),
+            gilrs::EventType::ButtonReleased(button, _) => (
+                state.gamepad_button_up_event(ctx, button, id),
+                ErrorOrigin::GamepadButtonUpEvent,
+            ),
+            gilrs::EventType::AxisChanged(axis, value, _) => (
+                state.gamepad_axis_event(ctx, axis, value, id),
+                ErrorOrigin::GamepadAxisEvent,
+            ),
+            _ => continue,
+        };
+        if catch_error(ctx, res, state, control_flow, origin) {
+            return true;
+        }
+    }
+    false
+}
+
+// Draw one frame, the same way ggez's own loop does
+fn draw_frame(ctx: &mut Context, state: &mut MainState) -> GameResult {
+    ctx.gfx.begin_frame()?;
+    let res = state.draw(ctx);
+    ctx.gfx.end_frame()?;
+    // reset the mouse delta and save the input state for the next frame
+    ctx.mouse.reset_delta();
+    ctx.keyboard.save_keyboard_state();
+    ctx.mouse.save_mouse_state();
+    res
+}
+
+// Hand an error to `on_error`, true if it was fatal and the loop is ending
+fn catch_error<T>(
+    ctx: &mut Context,
+    res: GameResult<T>,
+    state: &mut MainState,
+    control_flow: &mut ControlFlow,
+    origin: ErrorOrigin,
+) -> bool {
+    if let Err(e) = res {
+        eprintln!("Error on EventHandler {:?}: {:?}", origin, e);
+        if state.on_error(ctx, origin, e) {
+            *control_flow = ControlFlow::Exit;
+            return true;
+        }
+    }
+    false
+}
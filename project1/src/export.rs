@@ -0,0 +1,486 @@
+use crate::board::{Board, WordPosition};
+use ab_glyph::{Font, FontVec, PxScale};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tiny_skia::{FillRule, LineCap, Mask, Paint, PathBuilder, Pixmap, Rect, Stroke, Transform};
+
+// Font of the letters unless another one is given, built in so that exports work from anywhere
+pub(crate) const DEFAULT_FONT: &[u8] = include_bytes!("../resources/Montserrat-Bold.ttf");
+
+// Radius of the rings around the letters of a word that skips cells, in cells
+const RING_RADIUS: f32 = 0.4;
+
+#[derive(Debug)]
+pub enum ExportError {
+    Io(io::Error),
+    Font(String),
+    Render(String),
+}
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExportError::Io(e) => write!(f, "{}", e),
+            ExportError::Font(e) => write!(f, "invalid font: {}", e),
+            ExportError::Render(e) => write!(f, "could not render image: {}", e),
+        }
+    }
+}
+impl std::error::Error for ExportError {}
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        ExportError::Io(e)
+    }
+}
+
+/// A color with 8-bit channels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgba(pub u8, pub u8, pub u8, pub u8);
+impl Rgba {
+    /// Parse a `#RRGGBB` or `#RRGGBBAA` color
+    /// # Example
+    /// ```
+    /// use word_search_solver::export::Rgba;
+    /// assert_eq!(Rgba::from_hex("#0000ff"), Some(Rgba(0, 0, 255, 255)));
+    /// assert_eq!(Rgba::from_hex("ff000080"), Some(Rgba(255, 0, 0, 128)));
+    /// assert_eq!(Rgba::from_hex("#red"), None);
+    /// ```
+    pub fn from_hex(hex: &str) -> Option<Rgba> {
+        let hex = hex.trim_start_matches('#');
+        if (hex.len() != 6 && hex.len() != 8) || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+        Some(Rgba(channel(0)?, channel(2)?, channel(4)?, alpha))
+    }
+    // SVG attributes painting with this color, for `fill` or `stroke`
+    fn to_svg(self, attribute: &str) -> String {
+        format!(
+            "{a}=\"rgb({},{},{})\" {a}-opacity=\"{:.3}\"",
+            self.0,
+            self.1,
+            self.2,
+            self.3 as f32 / 255.0,
+            a = attribute
+        )
+    }
+    fn to_skia(self) -> tiny_skia::Color {
+        tiny_skia::Color::from_rgba8(self.0, self.1, self.2, self.3)
+    }
+}
+
+/// How a solved puzzle is drawn, the defaults match the visualizer
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    pub cell_size: f32,
+    pub margin: f32,
+    pub font_path: Option<PathBuf>, // TrueType font of the letters, Montserrat Bold if None
+    pub background: Rgba,
+    pub grid_color: Rgba,
+    pub letter_color: Rgba,
+    pub stroke_color: Rgba,
+    pub stroke_width: f32,
+    pub probe_color: Rgba, // Line being checked, only drawn in recorded frames
+    pub probe_cell_color: Rgba, // Single cell being checked
+}
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            cell_size: 50.0,
+            margin: 20.0,
+            font_path: None,
+            background: Rgba(255, 255, 255, 255),
+            grid_color: Rgba(255, 0, 0, 255),
+            letter_color: Rgba(0, 0, 0, 255),
+            stroke_color: Rgba(0, 0, 255, 255),
+            stroke_width: 5.0,
+            probe_color: Rgba(0, 255, 0, 255),
+            probe_cell_color: Rgba(255, 0, 0, 128),
+        }
+    }
+}
+impl RenderOptions {
+    /// Pick the largest cell size so the image of `board` fits in `width` x `height` pixels
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Board;
+    /// use word_search_solver::export::RenderOptions;
+    /// let board = Board::new(&vec![vec!['a'; 4]; 2]);
+    /// let mut options = RenderOptions::default();
+    /// options.fit(240, 240, &board);
+    /// assert_eq!(options.cell_size, 50.0);
+    /// ```
+    pub fn fit(&mut self, width: u32, height: u32, board: &Board) {
+        let cell_width = (width as f32 - self.margin * 2.0) / board.get_cols().max(1) as f32;
+        let cell_height = (height as f32 - self.margin * 2.0) / board.get_rows().max(1) as f32;
+        self.cell_size = cell_width.min(cell_height).floor().max(1.0);
+    }
+    fn size(&self, board: &Board) -> (f32, f32) {
+        (
+            self.margin * 2.0 + self.cell_size * board.get_cols() as f32,
+            self.margin * 2.0 + self.cell_size * board.get_rows() as f32,
+        )
+    }
+    // Center of a cell in image coordinates
+    fn cell_center(&self, (i, j): (usize, usize)) -> (f32, f32) {
+        (
+            self.margin + self.cell_size * (j as f32 + 0.5),
+            self.margin + self.cell_size * (i as f32 + 0.5),
+        )
+    }
+    fn font_size(&self) -> f32 {
+        self.cell_size * 0.6
+    }
+}
+
+/// Draw the board, its border and a stroke over every found word as an SVG document,
+/// the font is embedded so the file renders the same everywhere
+pub fn render_svg(
+    board: &Board,
+    found: &[WordPosition],
+    options: &RenderOptions,
+) -> Result<String, ExportError> {
+    let font_data = read_font(options.font_path.as_deref())?;
+    let (width, height) = options.size(board);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = width,
+        h = height
+    );
+    svg.push_str(&format!(
+        "<style>@font-face {{ font-family: \"PuzzleFont\"; src: url(data:font/ttf;base64,{}); }}</style>\n",
+        base64_encode(&font_data)
+    ));
+    svg.push_str(&format!(
+        "<rect width=\"{}\" height=\"{}\" {}/>\n",
+        width,
+        height,
+        options.background.to_svg("fill")
+    ));
+    svg.push_str(&format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke-width=\"1\" {}/>\n",
+        options.margin,
+        options.margin,
+        width - options.margin * 2.0,
+        height - options.margin * 2.0,
+        options.grid_color.to_svg("stroke")
+    ));
+    for (start, end) in found.iter().flat_map(WordPosition::strokes) {
+        let (x1, y1) = options.cell_center(start);
+        let (x2, y2) = options.cell_center(end);
+        if start == end {
+            // A word that skips cells has a ring around each of its letters
+            svg.push_str(&format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke-width=\"{}\" {}/>\n",
+                x1,
+                y1,
+                options.cell_size * RING_RADIUS,
+                options.stroke_width,
+                options.stroke_color.to_svg("stroke")
+            ));
+            continue;
+        }
+        svg.push_str(&format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke-width=\"{}\" stroke-linecap=\"round\" {}/>\n",
+            x1,
+            y1,
+            x2,
+            y2,
+            options.stroke_width,
+            options.stroke_color.to_svg("stroke")
+        ));
+    }
+    svg.push_str(&format!(
+        "<g font-family=\"PuzzleFont\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\" {}>\n",
+        options.font_size(),
+        options.letter_color.to_svg("fill")
+    ));
+    for (i, row) in board.letters.iter().enumerate() {
+        for (j, letter) in row.iter().enumerate() {
+            let (x, y) = options.cell_center((i, j));
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\">{}</text>\n",
+                x,
+                y,
+                escape_xml(*letter)
+            ));
+        }
+    }
+    svg.push_str("</g>\n</svg>\n");
+    Ok(svg)
+}
+
+/// Rasterize the same picture as `render_svg` and encode it as PNG, without any window
+pub fn render_png(
+    board: &Board,
+    found: &[WordPosition],
+    options: &RenderOptions,
+) -> Result<Vec<u8>, ExportError> {
+    FrameRenderer::new(options)?
+        .render(board, found, None)?
+        .encode_png()
+        .map_err(|e| ExportError::Render(e.to_string()))
+}
+
+/// Rasterizes frames of a board in memory, the font is read once for all of them
+pub struct FrameRenderer<'a> {
+    font: FontVec,
+    options: &'a RenderOptions,
+}
+impl<'a> FrameRenderer<'a> {
+    pub fn new(options: &'a RenderOptions) -> Result<Self, ExportError> {
+        let font = FontVec::try_from_vec(read_font(options.font_path.as_deref())?)
+            .map_err(|e| ExportError::Font(e.to_string()))?;
+        Ok(FrameRenderer { font, options })
+    }
+    /// Draw the board with a stroke over every found word, and the line being checked
+    /// by the solver if `probe` is set
+    pub fn render(
+        &self,
+        board: &Board,
+        found: &[WordPosition],
+        probe: Option<&WordPosition>,
+    ) -> Result<Pixmap, ExportError> {
+        let options = self.options;
+        let (width, height) = options.size(board);
+        let mut pixmap = Pixmap::new(width.ceil() as u32, height.ceil() as u32)
+            .ok_or_else(|| ExportError::Render(format!("invalid size {}x{}", width, height)))?;
+        pixmap.fill(options.background.to_skia());
+
+        // Border of the grid
+        let border = Rect::from_xywh(
+            options.margin,
+            options.margin,
+            width - options.margin * 2.0,
+            height - options.margin * 2.0,
+        )
+        .ok_or_else(|| ExportError::Render("empty board".to_string()))?;
+        let path = PathBuilder::from_rect(border);
+        let stroke = Stroke {
+            width: 1.0,
+            ..Stroke::default()
+        };
+        pixmap.stroke_path(
+            &path,
+            &paint(options.grid_color),
+            &stroke,
+            Transform::identity(),
+            None,
+        );
+
+        // Strokes over the found words
+        for word_position in found {
+            draw_line(
+                &mut pixmap,
+                word_position,
+                options.stroke_color,
+                options.stroke_width,
+                options,
+            );
+        }
+        // The line being checked, or a dot when it is a single cell, like in the visualizer
+        if let Some(probe) = probe {
+            if probe.start() == probe.end() {
+                let (x, y) = options.cell_center(probe.start());
+                if let Some(path) = PathBuilder::from_circle(x, y, options.cell_size / 2.0) {
+                    pixmap.fill_path(
+                        &path,
+                        &paint(options.probe_cell_color),
+                        FillRule::Winding,
+                        Transform::identity(),
+                        None,
+                    );
+                }
+            } else {
+                draw_line(
+                    &mut pixmap,
+                    probe,
+                    options.probe_color,
+                    options.stroke_width * 2.0,
+                    options,
+                );
+            }
+        }
+
+        self.draw_letters(&mut pixmap, board)?;
+        Ok(pixmap)
+    }
+    // Letters, rasterized into a coverage mask then filled with the letter color
+    fn draw_letters(&self, pixmap: &mut Pixmap, board: &Board) -> Result<(), ExportError> {
+        let options = self.options;
+        let mut mask = Mask::new(pixmap.width(), pixmap.height())
+            .ok_or_else(|| ExportError::Render("invalid mask size".to_string()))?;
+        let mask_width = mask.width() as i32;
+        let mask_height = mask.height() as i32;
+        let scale = PxScale::from(options.font_size());
+        for (i, row) in board.letters.iter().enumerate() {
+            for (j, letter) in row.iter().enumerate() {
+                let glyph = self
+                    .font
+                    .glyph_id(*letter)
+                    .with_scale_and_position(scale, ab_glyph::point(0.0, 0.0));
+                let Some(outline) = self.font.outline_glyph(glyph) else {
+                    continue;
+                };
+                let bounds = outline.px_bounds();
+                let (cx, cy) = options.cell_center((i, j));
+                let left = (cx - (bounds.min.x + bounds.max.x) / 2.0 + bounds.min.x).round() as i32;
+                let top = (cy - (bounds.min.y + bounds.max.y) / 2.0 + bounds.min.y).round() as i32;
+                let data = mask.data_mut();
+                outline.draw(|x, y, coverage| {
+                    let (px, py) = (left + x as i32, top + y as i32);
+                    if px >= 0 && py >= 0 && px < mask_width && py < mask_height {
+                        let idx = (py * mask_width + px) as usize;
+                        data[idx] = data[idx].max((coverage * 255.0).round() as u8);
+                    }
+                });
+            }
+        }
+        if let Some(rect) = Rect::from_xywh(0.0, 0.0, pixmap.width() as f32, pixmap.height() as f32)
+        {
+            let mut pb = PathBuilder::new();
+            pb.push_rect(rect);
+            if let Some(path) = pb.finish() {
+                pixmap.fill_path(
+                    &path,
+                    &paint(options.letter_color),
+                    FillRule::Winding,
+                    Transform::identity(),
+                    Some(&mask),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+// Stroke with round caps from the first to the last cell of a word,
+// or a ring around each letter of a word that skips cells
+fn draw_line(
+    pixmap: &mut Pixmap,
+    word_position: &WordPosition,
+    color: Rgba,
+    width: f32,
+    options: &RenderOptions,
+) {
+    let stroke = Stroke {
+        width,
+        line_cap: LineCap::Round,
+        ..Stroke::default()
+    };
+    for (start, end) in word_position.strokes() {
+        let (x1, y1) = options.cell_center(start);
+        if start == end {
+            if let Some(path) = PathBuilder::from_circle(x1, y1, options.cell_size * RING_RADIUS) {
+                pixmap.stroke_path(&path, &paint(color), &stroke, Transform::identity(), None);
+            }
+            continue;
+        }
+        let (x2, y2) = options.cell_center(end);
+        let mut pb = PathBuilder::new();
+        pb.move_to(x1, y1);
+        pb.line_to(x2, y2);
+        if let Some(path) = pb.finish() {
+            pixmap.stroke_path(&path, &paint(color), &stroke, Transform::identity(), None);
+        }
+    }
+}
+
+fn read_font(path: Option<&Path>) -> Result<Vec<u8>, ExportError> {
+    match path {
+        Some(path) => {
+            fs::read(path).map_err(|e| ExportError::Font(format!("{}: {}", path.display(), e)))
+        }
+        None => Ok(DEFAULT_FONT.to_vec()),
+    }
+}
+
+fn paint(color: Rgba) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color(color.to_skia());
+    paint.anti_alias = true;
+    paint
+}
+
+fn escape_xml(letter: char) -> String {
+    match letter {
+        '&' => "&amp;".to_string(),
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        _ => letter.to_string(),
+    }
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::abc_board;
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b"Man"), "TWFu");
+        assert_eq!(base64_encode(b"Ma"), "TWE=");
+        assert_eq!(base64_encode(b"M"), "TQ==");
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"Many"), "TWFueQ==");
+        assert_eq!(base64_encode(b"Many!"), "TWFueSE=");
+        assert_eq!(base64_encode(&[0xff, 0xfe, 0x00, 0x3f]), "//4APw==");
+    }
+    #[test]
+    fn test_render_svg() {
+        let (board, found) = (abc_board(), vec![WordPosition::new((0, 0), (2, 2))]);
+        let svg = render_svg(&board, &found, &RenderOptions::default()).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<text").count(), 9);
+        assert!(svg.contains("<line x1=\"45\" y1=\"45\" x2=\"145\" y2=\"145\""));
+    }
+    #[test]
+    fn test_render_png() {
+        let (board, found) = (abc_board(), vec![WordPosition::new((0, 0), (2, 2))]);
+        let options = RenderOptions {
+            cell_size: 20.0,
+            margin: 0.0,
+            ..RenderOptions::default()
+        };
+        let png = render_png(&board, &found, &options).unwrap();
+        let pixmap = Pixmap::decode_png(&png).unwrap();
+        assert_eq!((pixmap.width(), pixmap.height()), (60, 60));
+        // The stroke goes through the corner between the first two cells of the diagonal
+        let pixel = pixmap.pixel(20, 20).unwrap();
+        assert_eq!((pixel.red(), pixel.blue()), (0, 255));
+    }
+    #[test]
+    fn test_missing_font() {
+        let (board, found) = (abc_board(), vec![WordPosition::new((0, 0), (2, 2))]);
+        let options = RenderOptions {
+            font_path: Some(PathBuf::from("resources/missing.ttf")),
+            ..RenderOptions::default()
+        };
+        assert!(matches!(
+            render_png(&board, &found, &options),
+            Err(ExportError::Font(_))
+        ));
+    }
+}
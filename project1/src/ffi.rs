@@ -0,0 +1,348 @@
+// C API of the solver, its header include/word_search_solver.h is generated with cbindgen.
+// Every object created by a `ws_*_new` function or returned by `ws_solve` must be released
+// with its `ws_*_free` function. A function that fails returns NULL or false and keeps a
+// message for `ws_last_error`, per thread.
+use crate::api::{parse_grid, upper_word};
+use crate::board::{Board, WordPosition};
+use crate::solver;
+use crate::trie::Trie;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+use std::slice;
+
+/// A board, opaque to C
+pub struct WsBoard {
+    board: Board,
+}
+
+/// A trie of target words, opaque to C
+pub struct WsTrie {
+    trie: Trie,
+}
+
+/// The words found by `ws_solve`, opaque to C
+pub struct WsResults {
+    words: Vec<CString>,
+    positions: Vec<WordPosition>,
+}
+
+/// One word found in the board. `word` belongs to the results and is valid until they are freed
+#[repr(C)]
+pub struct WsMatch {
+    pub word: *const c_char,
+    pub start_row: usize,
+    pub start_col: usize,
+    pub end_row: usize,
+    pub end_col: usize,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    // A message cannot hold a NUL byte, this only happens if the input had one
+    let message = CString::new(message.replace('\0', " ")).expect("NUL bytes were removed");
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+}
+
+// Name the argument in the last error if it is NULL, true if it is
+fn is_null<T>(pointer: *const T, name: &str) -> bool {
+    if pointer.is_null() {
+        set_last_error(format!("the {} is NULL", name));
+    }
+    pointer.is_null()
+}
+
+// Read `len` bytes of UTF-8 text, NULL is only accepted for an empty buffer
+unsafe fn read_text<'a>(buffer: *const c_char, len: usize) -> Option<&'a str> {
+    if buffer.is_null() {
+        if len > 0 {
+            set_last_error("the buffer is NULL".to_string());
+            return None;
+        }
+        return Some("");
+    }
+    let bytes = slice::from_raw_parts(buffer as *const u8, len);
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Some(text),
+        Err(e) => {
+            set_last_error(format!("the buffer is not UTF-8: {}", e));
+            None
+        }
+    }
+}
+
+/// The message of the last failure on this thread, NULL if nothing failed yet.
+/// It is valid until the next failure on the same thread
+#[no_mangle]
+pub extern "C" fn ws_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Create a board from the `len` bytes of `buffer`, in the format of the board files:
+/// one row per line, spaces are ignored and letters are upper-cased.
+/// Returns NULL if the rows are not a rectangle of letters or it has more than 10000 cells
+///
+/// # Safety
+/// `buffer` must point to `len` readable bytes
+#[no_mangle]
+pub unsafe extern "C" fn ws_board_new(buffer: *const c_char, len: usize) -> *mut WsBoard {
+    let Some(text) = read_text(buffer, len) else {
+        return ptr::null_mut();
+    };
+    let rows: Vec<String> = text.lines().map(str::to_string).collect();
+    match parse_grid(&rows) {
+        Ok(board) => Box::into_raw(Box::new(WsBoard { board })),
+        Err(error) => {
+            set_last_error(error.message);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// The number of rows of the board, 0 if it is NULL
+///
+/// # Safety
+/// `board` must come from `ws_board_new` and not be freed
+#[no_mangle]
+pub unsafe extern "C" fn ws_board_rows(board: *const WsBoard) -> usize {
+    if is_null(board, "board") {
+        return 0;
+    }
+    (*board).board.get_rows()
+}
+
+/// The number of columns of the board, 0 if it is NULL
+///
+/// # Safety
+/// `board` must come from `ws_board_new` and not be freed
+#[no_mangle]
+pub unsafe extern "C" fn ws_board_cols(board: *const WsBoard) -> usize {
+    if is_null(board, "board") {
+        return 0;
+    }
+    (*board).board.get_cols()
+}
+
+/// Free a board, NULL is ignored
+///
+/// # Safety
+/// `board` must come from `ws_board_new` and not be freed yet
+#[no_mangle]
+pub unsafe extern "C" fn ws_board_free(board: *mut WsBoard) {
+    if !board.is_null() {
+        drop(Box::from_raw(board));
+    }
+}
+
+/// Create a trie from the `len` bytes of `buffer`, words separated by whitespace like the
+/// word files. Words are upper-cased to match the boards. Returns NULL if the text is not UTF-8
+///
+/// # Safety
+/// `buffer` must point to `len` readable bytes
+#[no_mangle]
+pub unsafe extern "C" fn ws_trie_new(buffer: *const c_char, len: usize) -> *mut WsTrie {
+    let Some(text) = read_text(buffer, len) else {
+        return ptr::null_mut();
+    };
+    let words: Vec<String> = text.split_whitespace().map(upper_word).collect();
+    let trie = Trie::from(&words.iter().map(String::as_str).collect());
+    Box::into_raw(Box::new(WsTrie { trie }))
+}
+
+/// Add a NUL-terminated word to the trie. Returns false if either is NULL or the word is not UTF-8
+///
+/// # Safety
+/// `trie` must come from `ws_trie_new` and `word` must be a NUL-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn ws_trie_insert(trie: *mut WsTrie, word: *const c_char) -> bool {
+    if is_null(trie, "trie") || is_null(word, "word") {
+        return false;
+    }
+    match CStr::from_ptr(word).to_str() {
+        Ok(word) => {
+            (*trie).trie.insert(&upper_word(word.trim()));
+            true
+        }
+        Err(e) => {
+            set_last_error(format!("the word is not UTF-8: {}", e));
+            false
+        }
+    }
+}
+
+/// Free a trie, NULL is ignored
+///
+/// # Safety
+/// `trie` must come from `ws_trie_new` and not be freed yet
+#[no_mangle]
+pub unsafe extern "C" fn ws_trie_free(trie: *mut WsTrie) {
+    if !trie.is_null() {
+        drop(Box::from_raw(trie));
+    }
+}
+
+/// Find every word of the trie in the board, free the results with `ws_results_free`.
+/// Returns NULL if the board or the trie is NULL
+///
+/// # Safety
+/// `board` and `trie` must come from `ws_board_new` and `ws_trie_new` and not be freed
+#[no_mangle]
+pub unsafe extern "C" fn ws_solve(board: *const WsBoard, trie: *const WsTrie) -> *mut WsResults {
+    if is_null(board, "board") || is_null(trie, "trie") {
+        return ptr::null_mut();
+    }
+    let board = &(*board).board;
+    let positions = solver::solve(board, &(*trie).trie);
+    let words = positions
+        .iter()
+        .map(|position| CString::new(board.get_word(position)).expect("letters are not NUL"))
+        .collect();
+    Box::into_raw(Box::new(WsResults { words, positions }))
+}
+
+/// The number of words found, 0 if the results handle is NULL
+///
+/// # Safety
+/// `results` must come from `ws_solve` and not be freed
+#[no_mangle]
+pub unsafe extern "C" fn ws_results_len(results: *const WsResults) -> usize {
+    if is_null(results, "results handle") {
+        return 0;
+    }
+    (*results).positions.len()
+}
+
+/// Fill `out` with the word at `index`, in the order the search found them.
+/// Returns false if either pointer is NULL or `index` is not below `ws_results_len`
+///
+/// # Safety
+/// `results` must come from `ws_solve` and not be freed, `out` must be writable
+#[no_mangle]
+pub unsafe extern "C" fn ws_results_get(
+    results: *const WsResults,
+    index: usize,
+    out: *mut WsMatch,
+) -> bool {
+    if is_null(results, "results handle") || is_null(out, "output match") {
+        return false;
+    }
+    let results = &*results;
+    let (Some(word), Some(position)) = (results.words.get(index), results.positions.get(index))
+    else {
+        set_last_error(format!(
+            "index {} is out of the {} results",
+            index,
+            results.positions.len()
+        ));
+        return false;
+    };
+    let ((start_row, start_col), (end_row, end_col)) = (position.start(), position.end());
+    *out = WsMatch {
+        word: word.as_ptr(),
+        start_row,
+        start_col,
+        end_row,
+        end_col,
+    };
+    true
+}
+
+/// Free the results and their words, NULL is ignored
+///
+/// # Safety
+/// `results` must come from `ws_solve` and not be freed yet
+#[no_mangle]
+pub unsafe extern "C" fn ws_results_free(results: *mut WsResults) {
+    if !results.is_null() {
+        drop(Box::from_raw(results));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(ws_last_error()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_solve_through_the_c_api() {
+        let board_text = "D O G\nX A X\n";
+        let words = "god cat";
+        unsafe {
+            let board = ws_board_new(board_text.as_ptr() as *const c_char, board_text.len());
+            let trie = ws_trie_new(words.as_ptr() as *const c_char, words.len());
+            assert!(ws_trie_insert(trie, c"do".as_ptr()));
+            assert_eq!((ws_board_rows(board), ws_board_cols(board)), (2, 3));
+            let results = ws_solve(board, trie);
+            assert_eq!(ws_results_len(results), 2);
+            let mut found = Vec::new();
+            for index in 0..ws_results_len(results) {
+                let mut word_match = WsMatch {
+                    word: ptr::null(),
+                    start_row: 0,
+                    start_col: 0,
+                    end_row: 0,
+                    end_col: 0,
+                };
+                assert!(ws_results_get(results, index, &mut word_match));
+                let word = CStr::from_ptr(word_match.word)
+                    .to_str()
+                    .unwrap()
+                    .to_string();
+                found.push((word, word_match.start_col, word_match.end_col));
+            }
+            found.sort();
+            assert_eq!(
+                found,
+                vec![("DO".to_string(), 0, 1), ("GOD".to_string(), 2, 0)]
+            );
+            let mut word_match = std::mem::zeroed();
+            assert!(!ws_results_get(results, 2, &mut word_match));
+            assert_eq!(last_error(), "index 2 is out of the 2 results");
+            ws_results_free(results);
+            ws_trie_free(trie);
+            ws_board_free(board);
+        }
+    }
+
+    #[test]
+    fn test_invalid_board() {
+        let board_text = "ABC\nAB";
+        unsafe {
+            let board = ws_board_new(board_text.as_ptr() as *const c_char, board_text.len());
+            assert!(board.is_null());
+            assert!(last_error().contains("same number of letters"));
+            let invalid = [0xff_u8];
+            assert!(ws_board_new(invalid.as_ptr() as *const c_char, 1).is_null());
+            assert!(last_error().starts_with("the buffer is not UTF-8"));
+            ws_board_free(board);
+        }
+    }
+
+    #[test]
+    fn test_null_handles() {
+        unsafe {
+            assert_eq!(ws_board_rows(ptr::null()), 0);
+            assert_eq!(last_error(), "the board is NULL");
+            assert_eq!(ws_board_cols(ptr::null()), 0);
+            assert!(!ws_trie_insert(ptr::null_mut(), c"do".as_ptr()));
+            assert_eq!(last_error(), "the trie is NULL");
+            assert!(ws_solve(ptr::null(), ptr::null()).is_null());
+            assert_eq!(ws_results_len(ptr::null()), 0);
+            assert!(!ws_results_get(ptr::null(), 0, ptr::null_mut()));
+            assert_eq!(last_error(), "the results handle is NULL");
+        }
+    }
+}
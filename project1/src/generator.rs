@@ -0,0 +1,341 @@
+use crate::board::{Board, Direction, WordPosition};
+use crate::constant::BOARD_SIZE;
+use crate::message::leftover_cells;
+use crate::solver::solve;
+use crate::trie::Trie;
+use std::fmt;
+
+const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// Letters placed so far, None where the cell is still empty
+type PartialGrid = Vec<Vec<Option<char>>>;
+
+/// How a puzzle is generated
+#[derive(Debug, Clone)]
+pub struct GeneratorOptions {
+    pub rows: usize,
+    pub cols: usize,
+    pub directions: Vec<Direction>, // Directions the words may be read in
+    pub seed: u64,                  // The same seed and words give the same puzzle
+    pub message: Option<String>,    // Hidden in the cells no word uses, its letters are read first
+    pub attempts: usize,            // Placements tried before giving up
+}
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        GeneratorOptions {
+            rows: BOARD_SIZE,
+            cols: BOARD_SIZE,
+            directions: Direction::iterator().copied().collect(),
+            seed: 0,
+            message: None,
+            attempts: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerateError {
+    EmptyBoard { rows: usize, cols: usize }, // One of the dimensions is zero
+    InvalidWord(String),
+    InvalidMessage(String), // The hidden message has characters other than letters and spaces
+    NoRoom(String),         // No placement was left for this word
+    MessageTooLong { letters: usize, leftover: usize },
+    Ambiguous, // The filler letters kept spelling target words again
+}
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenerateError::EmptyBoard { rows, cols } => {
+                write!(
+                    f,
+                    "a board of {} rows and {} columns has no cells",
+                    rows, cols
+                )
+            }
+            GenerateError::InvalidWord(word) => {
+                write!(f, "{:?} is not a word made of letters", word)
+            }
+            GenerateError::InvalidMessage(message) => {
+                write!(f, "{:?} is not a message made of letters", message)
+            }
+            GenerateError::NoRoom(word) => write!(f, "no room left for {}", word),
+            GenerateError::MessageTooLong { letters, leftover } => write!(
+                f,
+                "the message has {} letters but only {} cells are left",
+                letters, leftover
+            ),
+            GenerateError::Ambiguous => {
+                write!(f, "could not fill the board without repeating a word")
+            }
+        }
+    }
+}
+impl std::error::Error for GenerateError {}
+
+/// A generated board, with where each of its words was placed
+pub struct GeneratedPuzzle {
+    pub board: Board,
+    pub words: Vec<String>,
+    pub placements: Vec<WordPosition>, // Same order as `words`
+}
+impl GeneratedPuzzle {
+    /// The board in the format of the board files, letters separated by spaces
+    pub fn board_text(&self) -> String {
+        self.board
+            .letters
+            .iter()
+            .map(|row| row.iter().map(|c| format!("{} ", c)).collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+/// Place the words in a new board, then hide the message in the cells they leave, then fill
+/// the rest with random letters. Words and message are upper-cased, spaces in the message are dropped
+/// # Example
+/// ```
+/// use word_search_solver::generator::{generate, GeneratorOptions};
+/// use word_search_solver::message::hidden_message;
+/// let options = GeneratorOptions {
+///     rows: 5,
+///     cols: 5,
+///     message: Some("hi there".to_string()),
+///     ..GeneratorOptions::default()
+/// };
+/// let words = vec!["rust".to_string(), "trie".to_string()];
+/// let puzzle = generate(&words, &options).unwrap();
+/// assert!(hidden_message(&puzzle.board, &puzzle.placements).starts_with("HITHERE"));
+/// ```
+pub fn generate(
+    words: &[String],
+    options: &GeneratorOptions,
+) -> Result<GeneratedPuzzle, GenerateError> {
+    if options.rows == 0 || options.cols == 0 {
+        return Err(GenerateError::EmptyBoard {
+            rows: options.rows,
+            cols: options.cols,
+        });
+    }
+    let mut words_upper = Vec::new();
+    for word in words {
+        let word = word.trim().to_uppercase();
+        if word.is_empty() {
+            continue;
+        }
+        if !word.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(GenerateError::InvalidWord(word));
+        }
+        words_upper.push(word);
+    }
+    let message: Vec<char> = options
+        .message
+        .iter()
+        .flat_map(|message| message.chars())
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if !message.iter().all(|c| c.is_ascii_alphabetic()) {
+        return Err(GenerateError::InvalidMessage(message.into_iter().collect()));
+    }
+    let trie = Trie::from(&words_upper.iter().map(String::as_str).collect());
+    let mut rng = Rng::new(options.seed);
+    let mut error = GenerateError::Ambiguous;
+    for _ in 0..options.attempts.max(1) {
+        let (mut letters, placements) = match place_words(&words_upper, options, &mut rng) {
+            Ok(placed) => placed,
+            Err(e) => {
+                error = e;
+                continue;
+            }
+        };
+        let leftover: Vec<(usize, usize)> = (0..options.rows)
+            .flat_map(|i| (0..options.cols).map(move |j| (i, j)))
+            .filter(|&(i, j)| letters[i][j].is_none())
+            .collect();
+        if leftover.len() < message.len() {
+            error = GenerateError::MessageTooLong {
+                letters: message.len(),
+                leftover: leftover.len(),
+            };
+            continue;
+        }
+        for (k, &(i, j)) in leftover.iter().enumerate() {
+            letters[i][j] = Some(match message.get(k) {
+                Some(&c) => c,
+                None => ALPHABET[rng.below(ALPHABET.len())] as char,
+            });
+        }
+        let letters: Vec<Vec<char>> = letters
+            .into_iter()
+            .map(|row| row.into_iter().map(|c| c.unwrap_or('A')).collect())
+            .collect();
+        let mut board = Board::new(&letters);
+        // The solver reads the board in the directions the words were placed in
+        board.set_steps(options.directions.clone());
+        // A word spelled again by the filler would hide part of the message from the solver
+        if leftover_cells(&board, &solve(&board, &trie)) != leftover {
+            error = GenerateError::Ambiguous;
+            continue;
+        }
+        return Ok(GeneratedPuzzle {
+            board,
+            words: words_upper,
+            placements,
+        });
+    }
+    Err(error)
+}
+
+// Place the longest words first, each at a random position among those where it fits
+fn place_words(
+    words: &[String],
+    options: &GeneratorOptions,
+    rng: &mut Rng,
+) -> Result<(PartialGrid, Vec<WordPosition>), GenerateError> {
+    let mut letters = vec![vec![None; options.cols]; options.rows];
+    let mut placements = vec![WordPosition::new((0, 0), (0, 0)); words.len()];
+    let mut order: Vec<usize> = (0..words.len()).collect();
+    order.sort_by_key(|&idx| std::cmp::Reverse(words[idx].len()));
+    for idx in order {
+        let word: Vec<char> = words[idx].chars().collect();
+        let mut candidates = Vec::new();
+        for i in 0..options.rows {
+            for j in 0..options.cols {
+                for direction in &options.directions {
+                    if let Some(cells) = fit(&letters, &word, (i, j), direction) {
+                        candidates.push((cells, direction));
+                    }
+                }
+            }
+        }
+        if candidates.is_empty() {
+            return Err(GenerateError::NoRoom(words[idx].clone()));
+        }
+        let (cells, direction) = candidates.swap_remove(rng.below(candidates.len()));
+        for (&(i, j), &c) in cells.iter().zip(&word) {
+            letters[i][j] = Some(c);
+        }
+        placements[idx] = WordPosition::along(cells[0], direction, cells.len() as i32 - 1)
+            .expect("the word fits in the board");
+    }
+    Ok((letters, placements))
+}
+
+// Cells the word would cover, if it stays in the board and agrees with the letters already placed
+fn fit(
+    letters: &[Vec<Option<char>>],
+    word: &[char],
+    start: (usize, usize),
+    direction: &Direction,
+) -> Option<Vec<(usize, usize)>> {
+    let diff = direction.to_coord_diff();
+    let mut cells = Vec::new();
+    for (k, &c) in word.iter().enumerate() {
+        let i = usize::try_from(start.0 as i32 + diff.0 * k as i32).ok()?;
+        let j = usize::try_from(start.1 as i32 + diff.1 * k as i32).ok()?;
+        match letters.get(i)?.get(j)? {
+            Some(existing) if *existing != c => return None,
+            _ => cells.push((i, j)),
+        }
+    }
+    Some(cells)
+}
+
+// Small xorshift generator, so that a seed always gives the same puzzle
+struct Rng(u64);
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // The state of a xorshift generator must not be zero, the one seed mixed to zero gets another
+        match seed ^ 0x9E37_79B9_7F4A_7C15 {
+            0 => Rng(0x2545_F491_4F6C_DD1D),
+            state => Rng(state),
+        }
+    }
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::hidden_message;
+    use crate::test_utils::words;
+    #[test]
+    fn test_generated_words_are_found() {
+        let words = words(&["rust", "python", "java", "haskell", "lisp"]);
+        let options = GeneratorOptions {
+            rows: 8,
+            cols: 8,
+            seed: 7,
+            message: Some("Find me".to_string()),
+            ..GeneratorOptions::default()
+        };
+        let puzzle = generate(&words, &options).unwrap();
+        let trie = Trie::from(&puzzle.words.iter().map(String::as_str).collect());
+        let found = solve(&puzzle.board, &trie);
+        for (word, placement) in puzzle.words.iter().zip(&puzzle.placements) {
+            assert_eq!(&puzzle.board.get_word(placement), word);
+            assert!(found.contains(placement));
+        }
+        assert!(hidden_message(&puzzle.board, &found).starts_with("FINDME"));
+        // The same seed gives the same board
+        let again = generate(&words, &options).unwrap();
+        assert_eq!(again.board_text(), puzzle.board_text());
+    }
+    #[test]
+    fn test_generate_errors() {
+        let options = GeneratorOptions {
+            rows: 3,
+            cols: 3,
+            ..GeneratorOptions::default()
+        };
+        assert_eq!(
+            generate(&words(&["toolong"]), &options).err(),
+            Some(GenerateError::NoRoom("TOOLONG".to_string()))
+        );
+        assert_eq!(
+            generate(&words(&["c++"]), &options).err(),
+            Some(GenerateError::InvalidWord("C++".to_string()))
+        );
+        for (rows, cols) in [(0, 3), (3, 0)] {
+            let empty = GeneratorOptions {
+                rows,
+                cols,
+                ..options.clone()
+            };
+            assert_eq!(
+                generate(&[], &empty).err(),
+                Some(GenerateError::EmptyBoard { rows, cols })
+            );
+        }
+        let punctuated = GeneratorOptions {
+            message: Some("Hi!".to_string()),
+            ..options.clone()
+        };
+        assert_eq!(
+            generate(&words(&["abc"]), &punctuated).err(),
+            Some(GenerateError::InvalidMessage("HI!".to_string()))
+        );
+        // The seed that mixes to a zero state still gives numbers
+        let mut rng = Rng::new(0x9E37_79B9_7F4A_7C15);
+        assert_ne!(rng.next(), rng.next());
+        let options = GeneratorOptions {
+            message: Some("a message too long".to_string()),
+            attempts: 3,
+            ..options
+        };
+        assert_eq!(
+            generate(&words(&["abc"]), &options).err(),
+            Some(GenerateError::MessageTooLong {
+                letters: 15,
+                leftover: 6
+            })
+        );
+    }
+}
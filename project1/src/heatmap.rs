@@ -0,0 +1,153 @@
+use crate::board::{Board, WordPosition};
+use crate::state::search_state::SearchState;
+use crate::trie::Trie;
+
+/// How many times each cell of a board was touched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heatmap {
+    rows: usize,
+    cols: usize,
+    counts: Vec<usize>,
+}
+impl Heatmap {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Heatmap {
+            rows,
+            cols,
+            counts: vec![0; rows * cols],
+        }
+    }
+    /// Count how many search states touched each cell, a state touches every cell of the line it checks
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::Board;
+    /// use word_search_solver::heatmap::Heatmap;
+    /// use word_search_solver::trie::Trie;
+    /// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+    /// let heatmap = Heatmap::from_search(&board, &Trie::from(&vec!["ab"]));
+    /// // "a" checked in the 8 directions, then "ab", "ac" and "ad" since "a" starts a word
+    /// assert_eq!(heatmap.get(0, 0), 11);
+    /// // "d" in the 8 directions and "ad", the lines from "b" and "c" were pruned
+    /// assert_eq!(heatmap.get(1, 1), 9);
+    /// ```
+    pub fn from_search(board: &Board, trie: &Trie) -> Self {
+        let mut heatmap = Heatmap::new(board.get_rows(), board.get_cols());
+        let mut state = SearchState::new();
+        loop {
+            heatmap.add_state(board, &state);
+            board.check_state(&mut state, trie);
+            match board.next_state(&state, state.feasible) {
+                Some(next_state) => state = next_state,
+                None => break,
+            }
+        }
+        heatmap
+    }
+    /// Count how many found words pass through each cell
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::WordPosition;
+    /// use word_search_solver::heatmap::Heatmap;
+    /// let found = vec![WordPosition::new((0, 0), (0, 2)), WordPosition::new((0, 1), (2, 1))];
+    /// let heatmap = Heatmap::from_words(3, 3, &found);
+    /// assert_eq!(heatmap.get(0, 1), 2);
+    /// assert_eq!(heatmap.get(2, 2), 0);
+    /// assert_eq!(heatmap.max(), 2);
+    /// ```
+    pub fn from_words(rows: usize, cols: usize, found: &[WordPosition]) -> Self {
+        let mut heatmap = Heatmap::new(rows, cols);
+        for word_position in found {
+            heatmap.add_line(word_position);
+        }
+        heatmap
+    }
+    /// The line a search state checks, if it is inside the board
+    pub fn state_line(board: &Board, state: &SearchState) -> Option<WordPosition> {
+        let (i, j) = state.position;
+        board.get_string_from_direction(i, j, &state.direction, state.distance)?;
+        state.current_prefix()
+    }
+    /// Count the cells of the line checked by a search state
+    pub fn add_state(&mut self, board: &Board, state: &SearchState) {
+        if let Some(line) = Heatmap::state_line(board, state) {
+            self.add_line(&line);
+        }
+    }
+    /// Count every cell of a line once
+    pub fn add_line(&mut self, line: &WordPosition) {
+        for (i, j) in line.cells() {
+            if i < self.rows && j < self.cols {
+                self.counts[i * self.cols + j] += 1;
+            }
+        }
+    }
+    /// Undo `add_line`
+    pub fn remove_line(&mut self, line: &WordPosition) {
+        for (i, j) in line.cells() {
+            if i < self.rows && j < self.cols {
+                let count = &mut self.counts[i * self.cols + j];
+                *count = count.saturating_sub(1);
+            }
+        }
+    }
+    pub fn get(&self, i: usize, j: usize) -> usize {
+        self.counts[i * self.cols + j]
+    }
+    pub fn max(&self) -> usize {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+    /// Count of a cell relative to the hottest cell, between 0 and 1
+    pub fn intensity(&self, i: usize, j: usize) -> f32 {
+        let max = self.max();
+        if max == 0 {
+            return 0.0;
+        }
+        self.get(i, j) as f32 / max as f32
+    }
+    /// `intensity` of every cell, row by row
+    /// # Example
+    /// ```
+    /// use word_search_solver::board::WordPosition;
+    /// use word_search_solver::heatmap::Heatmap;
+    /// let found = vec![WordPosition::new((0, 0), (0, 1)), WordPosition::new((0, 1), (1, 1))];
+    /// let heatmap = Heatmap::from_words(2, 2, &found);
+    /// assert_eq!(heatmap.intensities(), vec![0.5, 1.0, 0.0, 0.5]);
+    /// ```
+    pub fn intensities(&self) -> Vec<f32> {
+        let max = self.max().max(1) as f32;
+        self.counts
+            .iter()
+            .map(|&count| count as f32 / max)
+            .collect()
+    }
+    pub fn get_rows(&self) -> usize {
+        self.rows
+    }
+    pub fn get_cols(&self) -> usize {
+        self.cols
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::solve;
+    use crate::test_utils::{abc_board, trie_of};
+    #[test]
+    fn test_words_heatmap_of_solved_board() {
+        let found = solve(&abc_board(), &trie_of(&["abc", "aei", "beh"]));
+        let heatmap = Heatmap::from_words(3, 3, &found);
+        assert_eq!(heatmap.get(1, 1), 2);
+        assert_eq!(heatmap.get(1, 0), 0);
+        assert_eq!(heatmap.intensity(1, 1), 1.0);
+        assert_eq!(heatmap.intensity(0, 2), 0.5);
+    }
+    #[test]
+    fn test_remove_line() {
+        let line = WordPosition::new((2, 0), (0, 2));
+        let mut heatmap = Heatmap::from_words(3, 3, std::slice::from_ref(&line));
+        heatmap.remove_line(&line);
+        heatmap.remove_line(&line);
+        assert_eq!(heatmap, Heatmap::new(3, 3));
+    }
+}
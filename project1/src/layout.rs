@@ -0,0 +1,164 @@
+// Share of the window width kept for the panels on each side of the grid
+const LEFT_PANEL_RATIO: f32 = 0.25;
+const RIGHT_PANEL_RATIO: f32 = 0.28;
+// Space above the grid and below it for the status line
+const TOP_RATIO: f32 = 1.0 / 9.0;
+const BOTTOM_MARGIN: f32 = 50.0;
+
+/// Position and size of everything drawn in the window, computed from the window
+/// size and the board dimensions so the grid always fits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout {
+    pub width: f32,
+    pub height: f32,
+    pub rows: usize,
+    pub cols: usize,
+    pub start_x: f32,   // Left of the grid
+    pub start_y: f32,   // Top of the grid
+    pub grid_size: f32, // Size of a cell
+}
+impl Layout {
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let layout = Layout::new(1600.0, 900.0, 15, 15);
+    /// assert_eq!(layout.grid_size, 50.0);
+    /// assert_eq!(layout.start_y, 100.0);
+    /// let layout = Layout::new(800.0, 600.0, 15, 15);
+    /// assert!(layout.grid_size * 15.0 <= 800.0 * 0.47);
+    /// ```
+    pub fn new(width: f32, height: f32, rows: usize, cols: usize) -> Self {
+        let left_panel = width * LEFT_PANEL_RATIO;
+        let start_y = height * TOP_RATIO;
+        let available_width = width * (1.0 - LEFT_PANEL_RATIO - RIGHT_PANEL_RATIO);
+        let available_height = height - start_y - BOTTOM_MARGIN;
+        let grid_size = (available_width / cols.max(1) as f32)
+            .min(available_height / rows.max(1) as f32)
+            .max(1.0);
+        Layout {
+            width,
+            height,
+            rows,
+            cols,
+            start_x: left_panel + (available_width - grid_size * cols as f32) / 2.0,
+            start_y,
+            grid_size,
+        }
+    }
+    /// Size of the whole grid
+    pub fn grid_dimensions(&self) -> (f32, f32) {
+        (
+            self.grid_size * self.cols as f32,
+            self.grid_size * self.rows as f32,
+        )
+    }
+    /// Center of a cell in window coordinates
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let layout = Layout::new(1600.0, 900.0, 15, 15);
+    /// let (x, y) = layout.cell_center(0.0, 1.0);
+    /// assert_eq!((x - layout.start_x, y), (25.0, 175.0));
+    /// ```
+    pub fn cell_center(&self, col: f32, row: f32) -> (f32, f32) {
+        (
+            self.start_x + self.grid_size * col + self.grid_size / 2.0,
+            self.start_y + self.grid_size * row + self.grid_size / 2.0,
+        )
+    }
+    /// Get the cell of the board under a point of the window
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let layout = Layout::new(1600.0, 900.0, 15, 15);
+    /// let (x, y) = layout.cell_center(3.0, 2.0);
+    /// assert_eq!(layout.cell_at(x, y), Some((2, 3)));
+    /// assert_eq!(layout.cell_at(0.0, 0.0), None);
+    /// ```
+    pub fn cell_at(&self, x: f32, y: f32) -> Option<(usize, usize)> {
+        let col = ((x - self.start_x) / self.grid_size).floor();
+        let row = ((y - self.start_y) / self.grid_size).floor();
+        if col < 0.0 || row < 0.0 || col >= self.cols as f32 || row >= self.rows as f32 {
+            return None;
+        }
+        Some((row as usize, col as usize))
+    }
+    /// Size of the letters in the grid and of the word lists
+    pub fn text_scale(&self) -> f32 {
+        self.grid_size * 0.6
+    }
+    /// Height of one entry of the word lists
+    pub fn line_height(&self) -> f32 {
+        self.grid_size
+    }
+    /// Left of the found words panel, on the right of the grid
+    pub fn word_panel_x(&self) -> f32 {
+        self.start_x + self.grid_dimensions().0 + self.width * 0.03
+    }
+    /// Height available to the word lists before they need to scroll
+    pub fn panel_height(&self) -> f32 {
+        self.height - self.start_y - BOTTOM_MARGIN
+    }
+    /// Top of the status line
+    pub fn status_y(&self) -> f32 {
+        self.height - BOTTOM_MARGIN + 10.0
+    }
+    /// Top of an entry of the word list scrolled by `scroll`, None if it is outside of the panel
+    pub fn word_entry_y(&self, idx: usize, scroll: f32) -> Option<f32> {
+        let y = self.line_height() * idx as f32 - scroll;
+        if y < 0.0 || y + self.line_height() > self.panel_height() {
+            return None;
+        }
+        Some(self.start_y + y)
+    }
+    /// Get the entry of the word list under a point of the window
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let layout = Layout::new(1600.0, 900.0, 15, 15);
+    /// let x = layout.word_panel_x() + 10.0;
+    /// assert_eq!(layout.word_entry_at(x, layout.start_y + 120.0, 0.0), Some(2));
+    /// assert_eq!(layout.word_entry_at(x, layout.start_y + 120.0, 50.0), Some(3));
+    /// assert_eq!(layout.word_entry_at(10.0, layout.start_y + 120.0, 0.0), None);
+    /// ```
+    pub fn word_entry_at(&self, x: f32, y: f32, scroll: f32) -> Option<usize> {
+        if x < self.word_panel_x() || y < self.start_y || y > self.start_y + self.panel_height() {
+            return None;
+        }
+        Some(((y - self.start_y + scroll) / self.line_height()) as usize)
+    }
+    /// Largest scroll offset of a list with `entries` entries
+    /// # Example
+    /// ```
+    /// use word_search_solver::layout::Layout;
+    /// let layout = Layout::new(1600.0, 900.0, 15, 15);
+    /// assert_eq!(layout.max_scroll(10), 0.0);
+    /// assert_eq!(layout.max_scroll(20), 250.0);
+    /// ```
+    pub fn max_scroll(&self, entries: usize) -> f32 {
+        (entries as f32 * self.line_height() - self.panel_height()).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_grid_fits_the_window() {
+        for (width, height) in [(800.0, 600.0), (1600.0, 900.0), (1600.0, 1200.0)] {
+            for (rows, cols) in [(15, 15), (5, 20), (30, 8)] {
+                let layout = Layout::new(width, height, rows, cols);
+                let (grid_width, grid_height) = layout.grid_dimensions();
+                assert!(layout.start_x >= width * LEFT_PANEL_RATIO);
+                assert!(layout.start_x + grid_width <= width * (1.0 - RIGHT_PANEL_RATIO) + 0.01);
+                assert!(layout.start_y + grid_height <= height - BOTTOM_MARGIN + 0.01);
+            }
+        }
+    }
+    #[test]
+    fn test_grid_grows_with_the_window() {
+        let small = Layout::new(800.0, 600.0, 15, 15);
+        let large = Layout::new(1600.0, 1200.0, 15, 15);
+        assert!(large.grid_size > small.grid_size);
+    }
+}
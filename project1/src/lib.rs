@@ -1,5 +1,38 @@
+pub mod api;
 pub mod board;
 pub mod constant;
+pub mod crossword;
+pub mod difficulty;
+#[cfg(feature = "gui")]
+pub mod event_loop;
+#[cfg(feature = "export")]
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod generator;
+pub mod heatmap;
+pub mod layout;
+pub mod loader;
+pub mod message;
+#[cfg(feature = "export")]
+pub mod pdf;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "export")]
+pub mod record;
+pub mod scrabble;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod solver;
 pub mod state;
+pub mod stats;
+#[cfg(test)]
+mod test_utils;
+#[cfg(feature = "gui")]
+pub mod theme;
+pub mod trace;
 pub mod trie;
+#[cfg(feature = "gui")]
 pub mod utils;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,136 @@
+use crate::board::Board;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+pub fn fetch_board(file_path: &Path) -> io::Result<Vec<Vec<char>>> {
+    let file = File::open(file_path)?;
+    let reader = BufReader::new(file);
+    let mut result = Vec::new();
+    for line in reader.lines().map_while(Result::ok) {
+        let mut vec_letter = Vec::new();
+        for c in line.chars() {
+            if c.is_alphabetic() {
+                vec_letter.push(c);
+            }
+        }
+        result.push(vec_letter.clone());
+    }
+    Ok(result)
+}
+
+pub fn fetch_target_words(file_path: &Path) -> io::Result<Vec<String>> {
+    let contents = fs::read_to_string(file_path)?;
+    let mut result = Vec::new();
+    for word in contents.split(' ') {
+        result.push(word.to_owned());
+    }
+    Ok(result)
+}
+
+/// Load a board and its target words, the board must be a non-empty rectangle of letters.
+/// Errors start with the path of the file that could not be loaded
+pub fn load_puzzle(board_path: &Path, words_path: &Path) -> io::Result<(Board, Vec<String>)> {
+    let load_error =
+        |path: &Path, e: io::Error| io::Error::new(e.kind(), format!("{}: {}", path.display(), e));
+    let letters: Vec<Vec<char>> = fetch_board(board_path)
+        .map_err(|e| load_error(board_path, e))?
+        .into_iter()
+        .filter(|row| !row.is_empty())
+        .collect();
+    if letters.is_empty() || letters.iter().any(|row| row.len() != letters[0].len()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "{}: every row of the board must have the same number of letters",
+                board_path.display()
+            ),
+        ));
+    }
+    let target_words = fetch_target_words(words_path).map_err(|e| load_error(words_path, e))?;
+    Ok((Board::new(&letters), target_words))
+}
+/// Load a crossword grid, keeping `black_square` along with the letters. Letters are upper-cased
+pub fn load_crossword(board_path: &Path, black_square: char) -> io::Result<Board> {
+    let contents = fs::read_to_string(board_path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", board_path.display(), e)))?;
+    let letters: Vec<Vec<char>> = contents
+        .lines()
+        .map(|line| {
+            line.chars()
+                .filter(|&c| c.is_alphabetic() || c == black_square)
+                .map(|c| c.to_ascii_uppercase())
+                .collect::<Vec<char>>()
+        })
+        .filter(|row| !row.is_empty())
+        .collect();
+    if letters.is_empty() || letters.iter().any(|row| row.len() != letters[0].len()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "{}: every row of the grid must have the same number of squares",
+                board_path.display()
+            ),
+        ));
+    }
+    Ok(Board::new(&letters))
+}
+// Guess whether a dropped file is a board (several rows of letters) rather than a word list
+pub fn is_board_file(file_path: &Path) -> bool {
+    fs::read_to_string(file_path)
+        .map(|contents| {
+            contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .count()
+                > 1
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::loader::{fetch_board, is_board_file, load_puzzle};
+    use crate::solver::SolvedPuzzle;
+    #[test]
+    fn ensure_board_input_exists() {
+        let file_path = Path::new("src/input/board.txt");
+        fetch_board(file_path).unwrap();
+    }
+    #[test]
+    fn ensure_target_words_input_exists() {
+        let file_path = Path::new("src/input/words.txt");
+        fetch_board(file_path).unwrap();
+    }
+    #[test]
+    fn test_load_puzzle_with_other_dimensions() {
+        let (board, target_words) = load_puzzle(
+            Path::new("src/input/board_months.txt"),
+            Path::new("src/input/months.txt"),
+        )
+        .unwrap();
+        assert_eq!((board.get_rows(), board.get_cols()), (15, 15));
+        assert_eq!(target_words.len(), 12);
+        let (board, target_words) = load_puzzle(
+            Path::new("src/input/board_planets.txt"),
+            Path::new("src/input/planets.txt"),
+        )
+        .unwrap();
+        assert_eq!((board.get_rows(), board.get_cols()), (6, 10));
+        assert_eq!(target_words.len(), 4);
+        let solved = SolvedPuzzle::new(board, target_words);
+        assert_eq!(solved.found.len(), 4);
+        assert!(load_puzzle(
+            Path::new("src/input/missing.txt"),
+            Path::new("src/input/months.txt")
+        )
+        .is_err());
+    }
+    #[test]
+    fn test_is_board_file() {
+        assert!(is_board_file(Path::new("src/input/board.txt")));
+        assert!(!is_board_file(Path::new("src/input/words.txt")));
+    }
+}
@@ -1,32 +1,425 @@
+#[cfg(feature = "gui")]
 use ggez::conf::WindowMode;
-use ggez::event::{self};
-use ggez::{ContextBuilder, GameResult};
-use std::{env, path};
+#[cfg(feature = "gui")]
+use ggez::{ContextBuilder, GameError, GameResult};
+use std::{env, fs, io, path};
+use word_search_solver::board::Direction;
+#[cfg(feature = "gui")]
 use word_search_solver::constant::*;
+use word_search_solver::crossword::{check_fill, entries, BLACK_SQUARE};
+use word_search_solver::difficulty::score_difficulty;
+#[cfg(feature = "gui")]
+use word_search_solver::event_loop;
+use word_search_solver::export::{render_png, render_svg, Rgba};
+use word_search_solver::generator::{generate, GeneratorOptions};
+use word_search_solver::loader::{fetch_target_words, load_crossword, load_puzzle};
+use word_search_solver::pdf::render_pdf;
+use word_search_solver::record::{record_gif, record_png_sequence, RecordOptions};
+use word_search_solver::solver::{solve_batch, SolvedPuzzle};
+#[cfg(feature = "gui")]
 use word_search_solver::state::main_state::MainState;
-fn main() -> GameResult {
-    // Make a Context.
-    let resource_dir = if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
-        let mut path = path::PathBuf::from(manifest_dir);
-        path.push("resources");
-        path
+#[cfg(feature = "gui")]
+use word_search_solver::theme::Theme;
+#[cfg(feature = "gui")]
+use word_search_solver::trace::read_trace;
+use word_search_solver::trace::{trace_search, write_trace};
+use word_search_solver::trie::Trie;
+
+const MAX_STEP: u32 = 100; // Longest jump of a --step, in cells
+const USAGE: &str = "Usage: word-search-solver [BOARD_FILE [WORDS_FILE]]... [OPTIONS]
+
+Without export options the first puzzle is solved in a window, when built with the gui feature.
+    -h, --help              Print this help
+    --theme FILE            Colors of the window, as `key = #RRGGBB` lines (keys: base, name,
+                            background, grid, letters, text, probe, probe_cell, missing, words)
+    --replay FILE           Replay the search states of a trace file instead of solving
+    --step ROWS,COLS        Also read words along this step, in each of its orientations,
+                            like 1,2 for knight moves or 0,2 for every other letter,
+                            at most 100 cells
+Export options, the solved puzzles are written without opening a window:
+    --svg FILE              Write the answer key of the first puzzle as SVG
+    --png FILE              Write the answer key of the first puzzle as PNG
+    --pdf FILE              Write every puzzle and its answer key as a PDF booklet
+    --difficulty            Print the difficulty score of every puzzle and what it is made of
+    --crossword             Check every board as a filled crossword grid, its word list being
+                            the dictionary, and print the entries that are not words
+    --black-square CHAR     Black squares of the crossword grids (default #)
+    --generate FILE         Write a new board for the word list of the first puzzle to FILE
+    --message TEXT          Hide TEXT in the cells of the generated board that no word uses
+    --seed N                Seed of the generated board (default 0)
+    --board-size COLSxROWS  Size of the generated board (default 15x15)
+    --trace FILE            Write every search state of the first puzzle as line-delimited JSON
+    --record-gif FILE       Record the solver animation of the first puzzle as an animated GIF
+    --record-png DIR        Record the solver animation as numbered PNG frames in DIR
+    --every N               Keep one search state out of N in the recording (default 1)
+    --fps N                 Frame rate of the recorded GIF (default 10)
+    --size WIDTHxHEIGHT     Fit the recorded frames in this many pixels
+    --cell-size SIZE        Size of a cell in pixels (default 50)
+    --font FILE             TrueType font for the letters (default the built-in Montserrat Bold)
+    --background COLOR      Colors as #RRGGBB or #RRGGBBAA
+    --grid-color COLOR
+    --letter-color COLOR
+    --stroke-color COLOR";
+
+struct Args {
+    puzzles: Vec<(path::PathBuf, path::PathBuf)>, // Board and word list files
+    svg_path: Option<path::PathBuf>,
+    png_path: Option<path::PathBuf>,
+    pdf_path: Option<path::PathBuf>,
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))] // Options of the window
+    theme_path: Option<path::PathBuf>,
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    replay_path: Option<path::PathBuf>,
+    trace_path: Option<path::PathBuf>,
+    record_gif_path: Option<path::PathBuf>,
+    record_png_dir: Option<path::PathBuf>,
+    record_options: RecordOptions,
+    difficulty: bool,
+    crossword: bool,
+    black_square: char,
+    generate_path: Option<path::PathBuf>,
+    generator_options: GeneratorOptions,
+    steps: Vec<Direction>, // Searched after the 8 directions
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut positional = Vec::new();
+    let mut svg_path = None;
+    let mut png_path = None;
+    let mut pdf_path = None;
+    let mut theme_path = None;
+    let mut replay_path = None;
+    let mut trace_path = None;
+    let mut record_gif_path = None;
+    let mut record_png_dir = None;
+    let mut record_options = RecordOptions::default();
+    let mut difficulty = false;
+    let mut crossword = false;
+    let mut black_square = BLACK_SQUARE;
+    let mut generate_path = None;
+    let mut generator_options = GeneratorOptions::default();
+    let mut steps = Vec::new();
+    let render_options = &mut record_options.render_options;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--help" || arg == "-h" {
+            println!("{}", USAGE);
+            std::process::exit(0);
+        }
+        if !arg.starts_with("--") {
+            positional.push(path::PathBuf::from(arg));
+            continue;
+        }
+        if arg == "--difficulty" {
+            difficulty = true;
+            continue;
+        }
+        if arg == "--crossword" {
+            crossword = true;
+            continue;
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", arg))?;
+        let color = || Rgba::from_hex(&value).ok_or_else(|| format!("invalid color {}", value));
+        match arg.as_str() {
+            "--svg" => svg_path = Some(path::PathBuf::from(&value)),
+            "--png" => png_path = Some(path::PathBuf::from(&value)),
+            "--pdf" => pdf_path = Some(path::PathBuf::from(&value)),
+            "--theme" => theme_path = Some(path::PathBuf::from(&value)),
+            "--replay" => replay_path = Some(path::PathBuf::from(&value)),
+            "--trace" => trace_path = Some(path::PathBuf::from(&value)),
+            "--record-gif" => record_gif_path = Some(path::PathBuf::from(&value)),
+            "--record-png" => record_png_dir = Some(path::PathBuf::from(&value)),
+            "--generate" => generate_path = Some(path::PathBuf::from(&value)),
+            "--message" => generator_options.message = Some(value),
+            "--step" => {
+                let (di, dj) = value
+                    .split_once(',')
+                    .and_then(|(di, dj)| Some((di.trim().parse().ok()?, dj.trim().parse().ok()?)))
+                    .filter(|&(di, dj): &(i32, i32)| {
+                        (2..=MAX_STEP).contains(&di.unsigned_abs().max(dj.unsigned_abs()))
+                    })
+                    .ok_or_else(|| format!("invalid step {}", value))?;
+                for step in [(di, dj), (dj, di)] {
+                    for (si, sj) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+                        let direction = Direction::Step(step.0 * si, step.1 * sj);
+                        if !steps.contains(&direction) {
+                            steps.push(direction);
+                        }
+                    }
+                }
+            }
+            "--black-square" => {
+                let mut chars = value.chars();
+                black_square = match (chars.next(), chars.next()) {
+                    (Some(c), None) if !c.is_alphabetic() => c,
+                    _ => return Err(format!("invalid black square {}", value)),
+                }
+            }
+            "--seed" => {
+                generator_options.seed = value
+                    .parse()
+                    .map_err(|_| format!("invalid seed {}", value))?
+            }
+            "--board-size" => {
+                let (cols, rows) = value
+                    .split_once('x')
+                    .and_then(|(cols, rows)| Some((cols.parse().ok()?, rows.parse().ok()?)))
+                    .filter(|&(cols, rows)| cols > 0 && rows > 0)
+                    .ok_or_else(|| format!("invalid board size {}", value))?;
+                generator_options.cols = cols;
+                generator_options.rows = rows;
+            }
+            "--every" => {
+                record_options.every = value
+                    .parse()
+                    .ok()
+                    .filter(|&every| every > 0)
+                    .ok_or_else(|| format!("invalid frame step {}", value))?
+            }
+            "--fps" => {
+                record_options.fps = value
+                    .parse()
+                    .ok()
+                    .filter(|&fps| fps > 0)
+                    .ok_or_else(|| format!("invalid frame rate {}", value))?
+            }
+            "--size" => {
+                let size = value
+                    .split_once('x')
+                    .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)))
+                    .ok_or_else(|| format!("invalid size {}", value))?;
+                record_options.size = Some(size)
+            }
+            "--cell-size" => {
+                render_options.cell_size = value
+                    .parse()
+                    .map_err(|_| format!("invalid cell size {}", value))?
+            }
+            "--font" => render_options.font_path = Some(path::PathBuf::from(&value)),
+            "--background" => render_options.background = color()?,
+            "--grid-color" => render_options.grid_color = color()?,
+            "--letter-color" => render_options.letter_color = color()?,
+            "--stroke-color" => render_options.stroke_color = color()?,
+            _ => return Err(format!("unknown option {}", arg)),
+        }
+    }
+    // Files come in board and word list pairs, a lone board uses the default word list
+    let mut puzzles: Vec<(path::PathBuf, path::PathBuf)> = positional
+        .chunks(2)
+        .map(|files| {
+            let words_path = files
+                .get(1)
+                .cloned()
+                .unwrap_or_else(|| crate_path("src/input/words.txt"));
+            (files[0].clone(), words_path)
+        })
+        .collect();
+    if puzzles.is_empty() {
+        puzzles.push((
+            crate_path("src/input/board.txt"),
+            crate_path("src/input/words.txt"),
+        ));
+    }
+    // Generated words may be placed along the steps too
+    generator_options.directions.extend(steps.iter().copied());
+    Ok(Args {
+        puzzles,
+        svg_path,
+        png_path,
+        pdf_path,
+        theme_path,
+        replay_path,
+        trace_path,
+        record_gif_path,
+        record_png_dir,
+        record_options,
+        difficulty,
+        crossword,
+        black_square,
+        generate_path,
+        generator_options,
+        steps,
+    })
+}
+
+// Generate a board from the word list of the first puzzle
+fn generate_board(args: &Args, board_path: &path::Path) -> Result<(), String> {
+    let words_path = &args.puzzles[0].1;
+    let words =
+        fetch_target_words(words_path).map_err(|e| format!("{}: {}", words_path.display(), e))?;
+    let puzzle = generate(&words, &args.generator_options).map_err(|e| e.to_string())?;
+    fs::write(board_path, puzzle.board_text())
+        .map_err(|e| format!("{}: {}", board_path.display(), e))?;
+    println!(
+        "Wrote {} with {} words",
+        board_path.display(),
+        puzzle.words.len()
+    );
+    Ok(())
+}
+
+// Check the fill of every board as a crossword grid against its word list
+fn check_crosswords(args: &Args) -> Result<(), String> {
+    for (board_path, words_path) in &args.puzzles {
+        let board = load_crossword(board_path, args.black_square).map_err(|e| e.to_string())?;
+        let dictionary = fs::read_to_string(words_path)
+            .map_err(|e| format!("{}: {}", words_path.display(), e))?
+            .to_uppercase();
+        let dictionary = Trie::from(&dictionary.split_whitespace().collect());
+        let invalid = check_fill(&board, &dictionary, args.black_square);
+        println!(
+            "{}: {} entries, {} not in the dictionary",
+            board_path.display(),
+            entries(&board, args.black_square).len(),
+            invalid.len()
+        );
+        for entry in invalid {
+            println!("    {}", entry);
+        }
+    }
+    Ok(())
+}
+
+// Solve the puzzles and write the requested files, without creating a window
+fn export(args: &Args) -> Result<(), String> {
+    let mut puzzles = Vec::new();
+    for (board_path, words_path) in &args.puzzles {
+        let (mut board, words) = load_puzzle(board_path, words_path).map_err(|e| e.to_string())?;
+        board.set_steps(args.steps.clone());
+        puzzles.push((board, words));
+    }
+    let solved: Vec<SolvedPuzzle> = solve_batch(puzzles);
+    let first = &solved[0];
+    let render_options = &args.record_options.render_options;
+    let export_error = |e: &dyn std::fmt::Display| e.to_string();
+    if let Some(svg_path) = &args.svg_path {
+        let svg =
+            render_svg(&first.board, &first.found, render_options).map_err(|e| export_error(&e))?;
+        fs::write(svg_path, svg).map_err(|e| export_error(&e))?;
+        println!("Wrote {}", svg_path.display());
+    }
+    if let Some(png_path) = &args.png_path {
+        let png =
+            render_png(&first.board, &first.found, render_options).map_err(|e| export_error(&e))?;
+        fs::write(png_path, png).map_err(|e| export_error(&e))?;
+        println!("Wrote {}", png_path.display());
+    }
+    if let Some(pdf_path) = &args.pdf_path {
+        fs::write(pdf_path, render_pdf(&solved)).map_err(|e| export_error(&e))?;
+        println!("Wrote {} with {} puzzles", pdf_path.display(), solved.len());
+    }
+    if args.difficulty {
+        for (puzzle, (board_path, _)) in solved.iter().zip(&args.puzzles) {
+            println!(
+                "{}\n{}",
+                board_path.display(),
+                score_difficulty(&puzzle.board, &puzzle.target_words)
+            );
+        }
+    }
+    let target_words = first.target_words.iter().map(String::as_str).collect();
+    let trie = Trie::from(&target_words);
+    if let Some(trace_path) = &args.trace_path {
+        let trace = trace_search(&first.board, &trie);
+        let file = fs::File::create(trace_path).map_err(|e| export_error(&e))?;
+        write_trace(&trace, io::BufWriter::new(file)).map_err(|e| export_error(&e))?;
+        println!("Wrote {} with {} states", trace_path.display(), trace.len());
+    }
+    if args.record_gif_path.is_some() || args.record_png_dir.is_some() {
+        if let Some(gif_path) = &args.record_gif_path {
+            let file = fs::File::create(gif_path).map_err(|e| export_error(&e))?;
+            let frames = record_gif(
+                &first.board,
+                &trie,
+                io::BufWriter::new(file),
+                &args.record_options,
+            )
+            .map_err(|e| export_error(&e))?;
+            println!("Wrote {} with {} frames", gif_path.display(), frames);
+        }
+        if let Some(png_dir) = &args.record_png_dir {
+            let frames = record_png_sequence(&first.board, &trie, png_dir, &args.record_options)
+                .map_err(|e| export_error(&e))?;
+            println!("Wrote {} frames to {}", frames, png_dir.display());
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+    let result = if let Some(board_path) = &args.generate_path {
+        generate_board(&args, board_path)
+    } else if args.crossword {
+        check_crosswords(&args)
+    } else if args.svg_path.is_some()
+        || args.png_path.is_some()
+        || args.pdf_path.is_some()
+        || args.record_gif_path.is_some()
+        || args.record_png_dir.is_some()
+        || args.trace_path.is_some()
+        || args.difficulty
+    {
+        export(&args)
     } else {
-        path::PathBuf::from("./resources")
+        run_window(&args).map_err(|e| e.to_string())
     };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+// Solve the first puzzle in a window, only returns if the window could not be created
+#[cfg(feature = "gui")]
+fn run_window(args: &Args) -> GameResult {
+    let (board_path, words_path) = args.puzzles[0].clone();
+    // Make a Context.
     let (mut ctx, event_loop) = ContextBuilder::new("my_game", "Cool Game Author")
         .window_mode(window_state_mode())
-        .add_resource_path(resource_dir)
         .build()
         .expect("aieee, could not create ggez context!");
     ctx.gfx.set_window_title("Word Search Visualizer");
     // Create an instance of your event handler.
     // Usually, you should provide it with the Context object to
     // use when setting your game up.
-    let state = MainState::new(&mut ctx)?;
+    let mut state = MainState::new(&mut ctx, &board_path, &words_path)?;
+    if !args.steps.is_empty() {
+        state.set_steps(args.steps.clone());
+    }
+    if let Some(theme_path) = &args.theme_path {
+        state.add_theme(&mut ctx, Theme::load(theme_path)?)?;
+    }
+    if let Some(replay_path) = &args.replay_path {
+        let trace = fs::File::open(replay_path)
+            .and_then(|file| read_trace(io::BufReader::new(file)))
+            .map_err(|e| {
+                GameError::ResourceLoadError(format!("{}: {}", replay_path.display(), e))
+            })?;
+        state.set_replay(trace)?;
+    }
 
     // Run!
-    event::run(ctx, event_loop, state);
+    event_loop::run(ctx, event_loop, state);
+}
+#[cfg(not(feature = "gui"))]
+fn run_window(_args: &Args) -> Result<(), String> {
+    Err("built without the gui feature, use one of the export options".to_string())
+}
+// Resolve a path relative to the crate directory, fixed at build time so that the default
+// puzzle loads whatever the working directory
+fn crate_path(relative: &str) -> path::PathBuf {
+    path::Path::new(env!("CARGO_MANIFEST_DIR")).join(relative)
 }
+#[cfg(feature = "gui")]
 pub fn window_state_mode() -> WindowMode {
     WindowMode {
         width: SCREEN_WIDTH,
@@ -0,0 +1,62 @@
+use crate::board::{Board, WordPosition};
+use crate::heatmap::Heatmap;
+use crate::trie::{AnagramOptions, Trie};
+
+/// Cells that no found word covers, in reading order: left to right, then top to bottom
+/// # Example
+/// ```
+/// use word_search_solver::board::{Board, WordPosition};
+/// use word_search_solver::message::leftover_cells;
+/// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+/// let found = vec![WordPosition::new((0, 0), (1, 1))];
+/// assert_eq!(leftover_cells(&board, &found), vec![(0, 1), (1, 0)]);
+/// ```
+pub fn leftover_cells(board: &Board, found: &[WordPosition]) -> Vec<(usize, usize)> {
+    let used = Heatmap::from_words(board.get_rows(), board.get_cols(), found);
+    (0..board.get_rows())
+        .flat_map(|i| (0..board.get_cols()).map(move |j| (i, j)))
+        .filter(|&(i, j)| used.get(i, j) == 0)
+        .collect()
+}
+
+/// Letters of the cells that no found word covers, in reading order. Word searches often hide
+/// a phrase there, to be read once every word is found
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::message::hidden_message;
+/// use word_search_solver::solver::solve;
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![vec!['C', 'A', 'T'], vec!['H', 'E', 'Y'], vec!['D', 'O', 'G']]);
+/// let found = solve(&board, &Trie::from(&vec!["CAT", "DOG"]));
+/// assert_eq!(hidden_message(&board, &found), "HEY");
+/// ```
+pub fn hidden_message(board: &Board, found: &[WordPosition]) -> String {
+    leftover_cells(board, found)
+        .into_iter()
+        .map(|(i, j)| board.letters[i][j])
+        .collect()
+}
+
+/// Words of the dictionary that can be written with the letters no found word covers, to
+/// design a bonus word or check what the hidden message could spell
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::message::leftover_anagrams;
+/// use word_search_solver::solver::solve;
+/// use word_search_solver::trie::{AnagramOptions, Trie};
+/// let board = Board::new(&vec![vec!['C', 'A', 'T'], vec!['H', 'E', 'Y'], vec!['D', 'O', 'G']]);
+/// let found = solve(&board, &Trie::from(&vec!["CAT", "DOG"]));
+/// let dictionary = Trie::from(&vec!["HEY", "YE", "HE", "HEN"]);
+/// let options = AnagramOptions::default();
+/// assert_eq!(leftover_anagrams(&board, &found, &dictionary, options), vec!["HEY", "HE", "YE"]);
+/// ```
+pub fn leftover_anagrams(
+    board: &Board,
+    found: &[WordPosition],
+    dictionary: &Trie,
+    options: AnagramOptions,
+) -> Vec<String> {
+    dictionary.anagrams(&hidden_message(board, found), options)
+}
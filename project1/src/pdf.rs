@@ -0,0 +1,238 @@
+use crate::board::{Board, WordPosition};
+use crate::solver::SolvedPuzzle;
+use pdf_writer::types::LineCapStyle;
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, Str};
+
+// A4 portrait, in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const PAGE_MARGIN: f32 = 40.0;
+const MAX_CELL_SIZE: f32 = 30.0;
+const GRID_TOP: f32 = PAGE_HEIGHT - 100.0;
+const GRID_MAX_HEIGHT: f32 = 480.0;
+const WORD_BANK_COLUMNS: usize = 3;
+const WORD_BANK_LINE_HEIGHT: f32 = 18.0;
+const WORD_BANK_BOTTOM: f32 = PAGE_MARGIN; // Lowest line of the word bank, above the page number
+const FONT: Name = Name(b"F1");
+const HIGHLIGHT_COLOR: (f32, f32, f32) = (1.0, 0.85, 0.3);
+
+/// Lay out a booklet with one puzzle page per puzzle (grid and word bank, continued on
+/// extra pages when it is too long), followed by one answer page per puzzle with the found
+/// words highlighted
+pub fn render_pdf(puzzles: &[SolvedPuzzle]) -> Vec<u8> {
+    let mut pages = Vec::new();
+    for (puzzle_idx, puzzle) in puzzles.iter().enumerate() {
+        let mut content = titled_page(&format!("Puzzle {}", puzzle_idx + 1));
+        let bottom = draw_grid(&mut content, puzzle, false);
+        let words: Vec<&str> = puzzle
+            .target_words
+            .iter()
+            .map(|word| word.trim())
+            .filter(|word| !word.is_empty())
+            .collect();
+        let mut rest = draw_word_bank(&mut content, &words, bottom - 30.0);
+        pages.push(content);
+        while !rest.is_empty() {
+            let mut content = titled_page(&format!("Puzzle {}, continued", puzzle_idx + 1));
+            rest = draw_word_bank(&mut content, rest, GRID_TOP);
+            pages.push(content);
+        }
+    }
+    for (puzzle_idx, puzzle) in puzzles.iter().enumerate() {
+        let mut content = titled_page(&format!("Answers {}", puzzle_idx + 1));
+        draw_grid(&mut content, puzzle, true);
+        pages.push(content);
+    }
+
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let font_id = Ref::new(3);
+    let page_count = pages.len();
+    // Every page needs an id for the page itself and one for its content stream
+    let page_ids: Vec<Ref> = (0..page_count)
+        .map(|i| Ref::new(4 + 2 * i as i32))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(page_count as i32);
+    pdf.type1_font(font_id)
+        .base_font(Name(b"Helvetica-Bold"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+    for (page_idx, (page_id, mut content)) in page_ids.iter().zip(pages).enumerate() {
+        let content_id = Ref::new(page_id.get() + 1);
+        let page_number = format!("Page {} of {}", page_idx + 1, page_count);
+        draw_text(
+            &mut content,
+            &page_number,
+            10.0,
+            (PAGE_WIDTH - text_width(&page_number, 10.0)) / 2.0,
+            PAGE_MARGIN / 2.0,
+        );
+
+        pdf.page(*page_id)
+            .parent(page_tree_id)
+            .media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+            .contents(content_id)
+            .resources()
+            .fonts()
+            .pair(FONT, font_id);
+        pdf.stream(content_id, &content.finish());
+    }
+    pdf.finish()
+}
+
+// A new page with its title at the top
+fn titled_page(title: &str) -> Content {
+    let mut content = Content::new();
+    draw_text(&mut content, title, 20.0, PAGE_MARGIN, PAGE_HEIGHT - 60.0);
+    content
+}
+
+// Largest cell size that fits the board in the grid area
+fn cell_size(board: &Board) -> f32 {
+    let width = (PAGE_WIDTH - PAGE_MARGIN * 2.0) / board.get_cols() as f32;
+    let height = GRID_MAX_HEIGHT / board.get_rows() as f32;
+    width.min(height).min(MAX_CELL_SIZE)
+}
+
+// Draw the border, the letters and optionally the highlighted answers, return the bottom of the grid
+fn draw_grid(content: &mut Content, puzzle: &SolvedPuzzle, answers: bool) -> f32 {
+    let board = &puzzle.board;
+    let cell = cell_size(board);
+    let width = cell * board.get_cols() as f32;
+    let height = cell * board.get_rows() as f32;
+    let left = (PAGE_WIDTH - width) / 2.0;
+    let bottom = GRID_TOP - height;
+    // PDF coordinates start at the bottom left of the page
+    let cell_center = |(i, j): (usize, usize)| {
+        (
+            left + cell * (j as f32 + 0.5),
+            GRID_TOP - cell * (i as f32 + 0.5),
+        )
+    };
+
+    if answers {
+        let (r, g, b) = HIGHLIGHT_COLOR;
+        content
+            .save_state()
+            .set_stroke_rgb(r, g, b)
+            .set_line_width(cell * 0.7)
+            .set_line_cap(LineCapStyle::RoundCap);
+        // With round caps, a line without length is a dot on a letter of a word that skips cells
+        for (start, end) in puzzle.found.iter().flat_map(WordPosition::strokes) {
+            let (x1, y1) = cell_center(start);
+            let (x2, y2) = cell_center(end);
+            content.move_to(x1, y1).line_to(x2, y2).stroke();
+        }
+        content.restore_state();
+    }
+
+    content
+        .set_line_width(1.0)
+        .rect(left, bottom, width, height)
+        .stroke();
+    let font_size = cell * 0.6;
+    for (i, row) in board.letters.iter().enumerate() {
+        for (j, letter) in row.iter().enumerate() {
+            let (x, y) = cell_center((i, j));
+            let letter = letter.to_string();
+            draw_text(
+                content,
+                &letter,
+                font_size,
+                x - text_width(&letter, font_size) / 2.0,
+                y - font_size * 0.35,
+            );
+        }
+    }
+    bottom
+}
+
+// Draw the words in columns from `top` down to the bottom of the page, return those left over
+fn draw_word_bank<'a>(content: &mut Content, words: &'a [&'a str], top: f32) -> &'a [&'a str] {
+    let max_rows = ((top - WORD_BANK_BOTTOM).max(0.0) / WORD_BANK_LINE_HEIGHT) as usize + 1;
+    let (words, rest) = words.split_at(words.len().min(max_rows * WORD_BANK_COLUMNS));
+    let column_width = (PAGE_WIDTH - PAGE_MARGIN * 2.0) / WORD_BANK_COLUMNS as f32;
+    let rows = words.len().div_ceil(WORD_BANK_COLUMNS);
+    for (idx, word) in words.iter().enumerate() {
+        let column = idx / rows.max(1);
+        let row = idx % rows.max(1);
+        draw_text(
+            content,
+            word,
+            12.0,
+            PAGE_MARGIN + column_width * column as f32,
+            top - WORD_BANK_LINE_HEIGHT * row as f32,
+        );
+    }
+    rest
+}
+
+fn draw_text(content: &mut Content, text: &str, size: f32, x: f32, y: f32) {
+    // The standard fonts only cover WinAnsi, anything else is replaced
+    let bytes: Vec<u8> = text
+        .chars()
+        .map(|c| if c.is_ascii() { c as u8 } else { b'?' })
+        .collect();
+    content
+        .begin_text()
+        .set_font(FONT, size)
+        .next_line(x, y)
+        .show(Str(&bytes))
+        .end_text();
+}
+
+// Width of a text in Helvetica-Bold, from the font metrics in thousandths of the font size
+fn text_width(text: &str, size: f32) -> f32 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c.to_ascii_uppercase() {
+            'I' => 278,
+            'J' => 556,
+            'E' | 'P' | 'S' | 'V' | 'X' | 'Y' => 667,
+            'F' | 'L' | 'T' | 'Z' => 611,
+            'G' | 'O' | 'Q' => 778,
+            'M' => 833,
+            'W' => 944,
+            ' ' => 278,
+            c if c.is_ascii_uppercase() => 722,
+            _ => 556,
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{abc_board, words};
+    #[test]
+    fn test_render_pdf_pages() {
+        let puzzle = || SolvedPuzzle::new(abc_board(), words(&["abc", "ie"]));
+        let pdf = render_pdf(&[puzzle(), puzzle()]);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-"));
+        assert!(text.contains("/Count 4"));
+        for title in ["(Puzzle 2)", "(Answers 1)", "(Page 4 of 4)", "(abc)"] {
+            assert!(text.contains(title), "missing {}", title);
+        }
+    }
+    #[test]
+    fn test_long_word_bank_continues() {
+        let bank: Vec<String> = (0..150).map(|k| format!("w{}", k)).collect();
+        let pdf = render_pdf(&[SolvedPuzzle::new(abc_board(), bank)]);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Count 3"));
+        for title in ["(Puzzle 1, continued)", "(w149)", "(Page 3 of 3)"] {
+            assert!(text.contains(title), "missing {}", title);
+        }
+    }
+    #[test]
+    fn test_text_width() {
+        assert!((text_width("MI", 10.0) - 11.11).abs() < 1e-4);
+    }
+}
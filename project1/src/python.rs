@@ -0,0 +1,219 @@
+use crate::api::{parse_grid, parse_words, upper_word};
+use crate::board::{Board, WordPosition};
+use crate::generator::{self, GeneratorOptions};
+use crate::loader::load_puzzle;
+use crate::message::hidden_message;
+use crate::solver::{self, find_missing_words};
+use crate::trie::Trie;
+use pyo3::create_exception;
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::path::Path;
+
+create_exception!(
+    word_search_solver,
+    GenerateError,
+    PyValueError,
+    "The words or the message do not fit in the generated board"
+);
+
+/// A rectangular grid of letters, built from a list of rows
+#[pyclass(name = "Board", module = "word_search_solver")]
+pub struct PyBoard {
+    board: Board,
+}
+#[pymethods]
+impl PyBoard {
+    /// Spaces in a row are ignored, letters are upper-cased. Raises ValueError if the
+    /// grid is empty, not rectangular or holds something else than letters
+    #[new]
+    fn new(grid: Vec<String>) -> PyResult<Self> {
+        let board = parse_grid(&grid).map_err(|e| PyValueError::new_err(e.message))?;
+        Ok(PyBoard { board })
+    }
+    /// Load a board file and its word list, returns the board and the words
+    #[staticmethod]
+    fn load(board_path: &str, words_path: &str) -> PyResult<(PyBoard, Vec<String>)> {
+        let (board, words) = load_puzzle(Path::new(board_path), Path::new(words_path))
+            .map_err(|e| PyIOError::new_err(e.to_string()))?;
+        Ok((PyBoard { board }, words))
+    }
+    #[getter]
+    fn rows(&self) -> usize {
+        self.board.get_rows()
+    }
+    #[getter]
+    fn cols(&self) -> usize {
+        self.board.get_cols()
+    }
+    /// The rows of the board as strings
+    fn grid(&self) -> Vec<String> {
+        grid(&self.board)
+    }
+    /// The letters from `start` to `end`, both (row, column) on the same line of the board
+    fn word(&self, start: (usize, usize), end: (usize, usize)) -> PyResult<String> {
+        let in_board =
+            |(i, j): (usize, usize)| i < self.board.get_rows() && j < self.board.get_cols();
+        let (di, dj) = (start.0.abs_diff(end.0), start.1.abs_diff(end.1));
+        if !in_board(start) || !in_board(end) || (di != 0 && dj != 0 && di != dj) {
+            return Err(PyValueError::new_err(format!(
+                "{:?} to {:?} is not a line of the board",
+                start, end
+            )));
+        }
+        Ok(self.board.get_word(&WordPosition::new(start, end)))
+    }
+    fn __repr__(&self) -> String {
+        format!("Board({:?})", grid(&self.board))
+    }
+}
+
+/// The words to look for, a prefix tree. Boards are upper-case, so are the words
+#[pyclass(name = "Trie", module = "word_search_solver")]
+pub struct PyTrie {
+    trie: Trie,
+}
+#[pymethods]
+impl PyTrie {
+    #[new]
+    #[pyo3(signature = (words = Vec::new()))]
+    fn new(words: Vec<String>) -> Self {
+        let words: Vec<String> = words.iter().map(|word| upper_word(word)).collect();
+        PyTrie {
+            trie: Trie::from(&words.iter().map(String::as_str).collect()),
+        }
+    }
+    fn insert(&mut self, word: &str) {
+        self.trie.insert(&upper_word(word));
+    }
+    fn search(&self, word: &str) -> bool {
+        self.trie.search(&upper_word(word))
+    }
+    fn starts_with(&self, prefix: &str) -> bool {
+        self.trie.starts_with(&upper_word(prefix))
+    }
+    fn __contains__(&self, word: &str) -> bool {
+        self.search(word)
+    }
+}
+
+/// Every word of the trie read in the board, as dicts with `word`, `start` and `end`
+#[pyfunction]
+fn solve<'py>(
+    py: Python<'py>,
+    board: &PyBoard,
+    trie: &PyTrie,
+) -> PyResult<Vec<Bound<'py, PyDict>>> {
+    solver::solve(&board.board, &trie.trie)
+        .iter()
+        .map(|word_position| match_dict(py, &board.board, word_position))
+        .collect()
+}
+
+/// The words that cannot be read in the board, as dicts with `word`, the longest `prefix`
+/// that can be read and its `start` and `end`, None if not even the first letter is there
+#[pyfunction]
+fn find_missing<'py>(
+    py: Python<'py>,
+    board: &PyBoard,
+    words: Vec<String>,
+) -> PyResult<Vec<Bound<'py, PyDict>>> {
+    let words = parse_words(&words).map_err(|e| PyValueError::new_err(e.message))?;
+    let found = solver::solve(
+        &board.board,
+        &Trie::from(&words.iter().map(String::as_str).collect()),
+    );
+    find_missing_words(&board.board, &words, &found)
+        .into_iter()
+        .map(|missing_word| {
+            let dict = PyDict::new(py);
+            dict.set_item("word", missing_word.word)?;
+            dict.set_item("prefix", missing_word.prefix)?;
+            let position = missing_word.position.as_ref();
+            dict.set_item("start", position.map(WordPosition::start))?;
+            dict.set_item("end", position.map(WordPosition::end))?;
+            Ok(dict)
+        })
+        .collect()
+}
+
+/// The letters that no word of the trie covers, in reading order
+#[pyfunction]
+fn leftover_letters(board: &PyBoard, trie: &PyTrie) -> String {
+    hidden_message(&board.board, &solver::solve(&board.board, &trie.trie))
+}
+
+/// Generate a puzzle, returns a dict with the `board` and the `placements` of the words.
+/// Raises GenerateError if they do not fit
+#[pyfunction]
+#[pyo3(signature = (words, rows = 15, cols = 15, seed = 0, message = None))]
+fn generate<'py>(
+    py: Python<'py>,
+    words: Vec<String>,
+    rows: usize,
+    cols: usize,
+    seed: u64,
+    message: Option<String>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let words = parse_words(&words).map_err(|e| PyValueError::new_err(e.message))?;
+    if rows == 0 || cols == 0 {
+        return Err(PyValueError::new_err("rows and cols must be positive"));
+    }
+    let options = GeneratorOptions {
+        rows,
+        cols,
+        seed,
+        message,
+        ..GeneratorOptions::default()
+    };
+    let puzzle =
+        generator::generate(&words, &options).map_err(|e| GenerateError::new_err(e.to_string()))?;
+    let placements = puzzle
+        .placements
+        .iter()
+        .map(|word_position| match_dict(py, &puzzle.board, word_position))
+        .collect::<PyResult<Vec<_>>>()?;
+    let dict = PyDict::new(py);
+    dict.set_item(
+        "board",
+        PyBoard {
+            board: puzzle.board,
+        },
+    )?;
+    dict.set_item("placements", placements)?;
+    Ok(dict)
+}
+
+fn match_dict<'py>(
+    py: Python<'py>,
+    board: &Board,
+    word_position: &WordPosition,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("word", board.get_word(word_position))?;
+    dict.set_item("start", word_position.start())?;
+    dict.set_item("end", word_position.end())?;
+    Ok(dict)
+}
+
+fn grid(board: &Board) -> Vec<String> {
+    board
+        .letters
+        .iter()
+        .map(|row| row.iter().collect())
+        .collect()
+}
+
+/// The `word_search_solver` Python module
+#[pymodule]
+fn word_search_solver(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyBoard>()?;
+    m.add_class::<PyTrie>()?;
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(find_missing, m)?)?;
+    m.add_function(wrap_pyfunction!(leftover_letters, m)?)?;
+    m.add_function(wrap_pyfunction!(generate, m)?)?;
+    m.add("GenerateError", m.py().get_type::<GenerateError>())?;
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use crate::board::Board;
+use crate::export::{ExportError, FrameRenderer, RenderOptions};
+use crate::state::search_state::SearchState;
+use crate::trie::Trie;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use tiny_skia::Pixmap;
+
+/// How the solver animation is recorded
+pub struct RecordOptions {
+    pub every: usize,             // Keep one search state out of `every`
+    pub fps: u32,                 // Frames per second of the animated GIF
+    pub size: Option<(u32, u32)>, // Fit the frames in this many pixels, else use the cell size
+    pub render_options: RenderOptions,
+}
+impl Default for RecordOptions {
+    fn default() -> Self {
+        RecordOptions {
+            every: 1,
+            fps: 10,
+            size: None,
+            render_options: RenderOptions::default(),
+        }
+    }
+}
+
+/// Visit the search states the way the visualizer does, without any window, and pass
+/// one frame out of `options.every` to `on_frame`. The last frame shows the solved board
+/// # Returns
+/// * `usize` - The number of frames
+pub fn record_frames(
+    board: &Board,
+    trie: &Trie,
+    options: &RecordOptions,
+    mut on_frame: impl FnMut(Pixmap) -> Result<(), ExportError>,
+) -> Result<usize, ExportError> {
+    let mut render_options = options.render_options.clone();
+    if let Some((width, height)) = options.size {
+        render_options.fit(width, height, board);
+    }
+    let renderer = FrameRenderer::new(&render_options)?;
+    let mut state = SearchState::new();
+    let mut found = Vec::new();
+    let mut frames = 0;
+    for step in 0.. {
+        let probe = state.current_prefix();
+        if let Some(word_position) = board.check_state(&mut state, trie) {
+            found.push(word_position);
+        }
+        if step % options.every.max(1) == 0 {
+            // Like the visualizer, a line that cannot start a word is not drawn
+            let probe = probe.filter(|_| state.feasible);
+            on_frame(renderer.render(board, &found, probe.as_ref())?)?;
+            frames += 1;
+        }
+        match board.next_state(&state, state.feasible) {
+            Some(next_state) => state = next_state,
+            None => break,
+        }
+    }
+    on_frame(renderer.render(board, &found, None)?)?;
+    Ok(frames + 1)
+}
+
+/// Record the animation as `frame_00000.png`, `frame_00001.png`... in `dir`
+pub fn record_png_sequence(
+    board: &Board,
+    trie: &Trie,
+    dir: &Path,
+    options: &RecordOptions,
+) -> Result<usize, ExportError> {
+    fs::create_dir_all(dir)?;
+    let mut idx = 0;
+    record_frames(board, trie, options, |pixmap| {
+        let png = pixmap
+            .encode_png()
+            .map_err(|e| ExportError::Render(e.to_string()))?;
+        fs::write(dir.join(format!("frame_{:05}.png", idx)), png)?;
+        idx += 1;
+        Ok(())
+    })
+}
+
+/// Record the animation as an animated GIF looping forever, at `options.fps` frames per second
+pub fn record_gif<W: Write>(
+    board: &Board,
+    trie: &Trie,
+    writer: W,
+    options: &RecordOptions,
+) -> Result<usize, ExportError> {
+    let gif_error = |e: gif::EncodingError| ExportError::Render(e.to_string());
+    // GIF delays are in hundredths of a second
+    let delay = (100 / options.fps.max(1)).max(1) as u16;
+    let mut writer = Some(writer);
+    let mut encoder: Option<gif::Encoder<W>> = None;
+    record_frames(board, trie, options, |pixmap| {
+        let (width, height) = (pixmap.width(), pixmap.height());
+        let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Err(ExportError::Render(format!(
+                "frames of {}x{} are too large for a GIF",
+                width, height
+            )));
+        };
+        if encoder.is_none() {
+            let writer = writer.take().expect("the encoder is created once");
+            let mut new_encoder =
+                gif::Encoder::new(writer, width, height, &[]).map_err(gif_error)?;
+            new_encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(gif_error)?;
+            encoder = Some(new_encoder);
+        }
+        // GIF colors are straight RGBA, a translucent background would darken them otherwise
+        let mut pixels: Vec<u8> = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let color = pixel.demultiply();
+                [color.red(), color.green(), color.blue(), color.alpha()]
+            })
+            .collect();
+        let mut frame = gif::Frame::from_rgba_speed(width, height, &mut pixels, 10);
+        frame.delay = delay;
+        encoder
+            .as_mut()
+            .expect("created above")
+            .write_frame(&frame)
+            .map_err(gif_error)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::Rgba;
+    use crate::test_utils::{abc_board, trie_of};
+    #[test]
+    fn test_record_every_nth_frame() {
+        let (board, trie) = (abc_board(), trie_of(&["abc", "ei"]));
+        let all = record_frames(&board, &trie, &RecordOptions::default(), |_| Ok(())).unwrap();
+        let options = RecordOptions {
+            every: 4,
+            ..RecordOptions::default()
+        };
+        let some = record_frames(&board, &trie, &options, |_| Ok(())).unwrap();
+        // Every state plus the final frame
+        assert_eq!(some, (all - 1).div_ceil(4) + 1);
+    }
+    #[test]
+    fn test_record_size() {
+        let (board, trie) = (abc_board(), trie_of(&["abc", "ei"]));
+        let options = RecordOptions {
+            every: 1000,
+            size: Some((200, 100)),
+            ..RecordOptions::default()
+        };
+        record_frames(&board, &trie, &options, |pixmap| {
+            assert!(pixmap.width() <= 200 && pixmap.height() <= 100);
+            assert_eq!(pixmap.height(), 100);
+            Ok(())
+        })
+        .unwrap();
+    }
+    #[test]
+    fn test_record_gif() {
+        let (board, trie) = (abc_board(), trie_of(&["abc", "ei"]));
+        let options = RecordOptions {
+            every: 10,
+            fps: 20,
+            ..RecordOptions::default()
+        };
+        let mut gif = Vec::new();
+        let frames = record_gif(&board, &trie, &mut gif, &options).unwrap();
+        assert!(frames > 1);
+        assert!(gif.starts_with(b"GIF89a"));
+    }
+    #[test]
+    fn test_record_gif_translucent_background() {
+        let (board, trie) = (abc_board(), trie_of(&["abc"]));
+        let mut options = RecordOptions {
+            every: 100,
+            ..RecordOptions::default()
+        };
+        options.render_options.background = Rgba(255, 255, 255, 128);
+        let mut gif = Vec::new();
+        record_gif(&board, &trie, &mut gif, &options).unwrap();
+        let mut decode_options = gif::DecodeOptions::new();
+        decode_options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = decode_options.read_info(&gif[..]).unwrap();
+        let frame = decoder.read_next_frame().unwrap().unwrap();
+        // The background keeps its color instead of being darkened by its alpha
+        let brightest = frame.buffer.chunks(4).map(|pixel| pixel[0]).max();
+        assert!(brightest.unwrap() > 240);
+    }
+}
@@ -0,0 +1,470 @@
+use crate::board::{Board, Direction, WordPosition};
+use crate::trie::Trie;
+
+/// An empty square of a partially filled board
+pub const EMPTY_SQUARE: char = '.';
+/// A blank tile of the rack, it can be played as any letter and scores nothing
+pub const BLANK_TILE: char = '?';
+/// Bonus for playing a whole rack in one move
+pub const BINGO_BONUS: u32 = 50;
+const RACK_SIZE: usize = 7;
+
+// The classic 15x15 board, in the format of `PremiumLayout::parse`
+const STANDARD_LAYOUT: &str = "\
+T..d...T...d..T
+.D...t...t...D.
+..D...d.d...D..
+d..D...d...D..d
+....D.....D....
+.t...t...t...t.
+..d...d.d...d..
+T..d...D...d..T
+..d...d.d...d..
+.t...t...t...t.
+....D.....D....
+d..D...d...D..d
+..D...d.d...D..
+.D...t...t...D.
+T..d...T...d..T";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Premium {
+    #[default]
+    Normal,
+    DoubleLetter,
+    TripleLetter,
+    DoubleWord,
+    TripleWord,
+}
+impl Premium {
+    fn letter_multiplier(&self) -> u32 {
+        match self {
+            Premium::DoubleLetter => 2,
+            Premium::TripleLetter => 3,
+            _ => 1,
+        }
+    }
+    fn word_multiplier(&self) -> u32 {
+        match self {
+            Premium::DoubleWord => 2,
+            Premium::TripleWord => 3,
+            _ => 1,
+        }
+    }
+}
+
+/// The premium squares of a board. Squares outside of the layout are normal
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PremiumLayout {
+    squares: Vec<Vec<Premium>>,
+}
+impl PremiumLayout {
+    /// One row per line: `.` is a normal square, `d` and `t` double and triple the letter,
+    /// `D` and `T` double and triple the word
+    /// # Example
+    /// ```
+    /// use word_search_solver::scrabble::{Premium, PremiumLayout};
+    /// let layout = PremiumLayout::parse("T.d\n.D.").unwrap();
+    /// assert_eq!(layout.get(0, 0), Premium::TripleWord);
+    /// assert_eq!(layout.get(1, 1), Premium::DoubleWord);
+    /// assert_eq!(layout.get(5, 5), Premium::Normal);
+    /// assert!(PremiumLayout::parse("x").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let squares = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                line.trim()
+                    .chars()
+                    .map(|c| match c {
+                        '.' => Ok(Premium::Normal),
+                        'd' => Ok(Premium::DoubleLetter),
+                        't' => Ok(Premium::TripleLetter),
+                        'D' => Ok(Premium::DoubleWord),
+                        'T' => Ok(Premium::TripleWord),
+                        _ => Err(format!("unknown premium square {:?}", c)),
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(PremiumLayout { squares })
+    }
+    /// The premium squares of the classic 15x15 board
+    pub fn standard() -> Self {
+        PremiumLayout::parse(STANDARD_LAYOUT).expect("the standard layout is valid")
+    }
+    pub fn get(&self, i: usize, j: usize) -> Premium {
+        self.squares
+            .get(i)
+            .and_then(|row| row.get(j))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Points of a tile in the English letter distribution, blanks and other characters score 0
+pub fn letter_score(letter: char) -> u32 {
+    match letter.to_ascii_uppercase() {
+        'A' | 'E' | 'I' | 'L' | 'N' | 'O' | 'R' | 'S' | 'T' | 'U' => 1,
+        'D' | 'G' => 2,
+        'B' | 'C' | 'M' | 'P' => 3,
+        'F' | 'H' | 'V' | 'W' | 'Y' => 4,
+        'K' => 5,
+        'J' | 'X' => 8,
+        'Q' | 'Z' => 10,
+        _ => 0,
+    }
+}
+
+/// A tile of the rack put on an empty square
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacedTile {
+    pub cell: (usize, usize),
+    pub letter: char,
+    /// Whether a blank tile stands for the letter
+    pub blank: bool,
+}
+
+/// A legal move: the word along the line, the tiles it puts down and what it scores
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub word: String,
+    pub position: WordPosition,
+    /// `Direction::Right` or `Direction::Down`
+    pub direction: Direction,
+    pub tiles: Vec<PlacedTile>,
+    /// The words formed across the line by the placed tiles
+    pub cross_words: Vec<String>,
+    pub score: u32,
+}
+
+// What does not change while looking for the moves of a board
+struct MoveSearch<'a> {
+    board: &'a Board,
+    layout: &'a PremiumLayout,
+    dictionary: &'a Trie,
+    direction: Direction,
+    cross_direction: Direction,
+}
+
+/// The `count` best scoring moves for the rack, best first. Empty squares of the board are
+/// `EMPTY_SQUARE`, blank tiles of the rack `BLANK_TILE`. A move puts tiles in one row or
+/// column, forms a word of the dictionary with the letters already there and with every word
+/// it crosses, and touches a letter of the board. On an empty board it covers the center
+/// # Example
+/// ```
+/// use word_search_solver::board::{Board, Direction};
+/// use word_search_solver::scrabble::{best_moves, PremiumLayout};
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![
+///     vec!['.', '.', '.'],
+///     vec!['.', 'A', '.'],
+///     vec!['.', '.', '.'],
+/// ]);
+/// let dictionary = Trie::from(&vec!["AT", "CAT", "TA"]);
+/// let moves = best_moves(&board, "CT", &PremiumLayout::default(), &dictionary, 2);
+/// assert_eq!(moves[0].word, "CAT");
+/// assert_eq!(moves[0].score, 5);
+/// assert_eq!(moves.len(), 2);
+/// ```
+pub fn best_moves(
+    board: &Board,
+    rack: &str,
+    layout: &PremiumLayout,
+    dictionary: &Trie,
+    count: usize,
+) -> Vec<Move> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let mut rack: Vec<char> = rack.chars().map(|c| c.to_ascii_uppercase()).collect();
+    let empty_board = (0..board.get_rows())
+        .all(|i| (0..board.get_cols()).all(|j| letter_at(board, (i, j)).is_none()));
+    let center = (board.get_rows() / 2, board.get_cols() / 2);
+    let mut moves: Vec<Move> = Vec::new();
+    for (direction, cross_direction) in [
+        (Direction::Right, Direction::Down),
+        (Direction::Down, Direction::Right),
+    ] {
+        let search = MoveSearch {
+            board,
+            layout,
+            dictionary,
+            direction,
+            cross_direction,
+        };
+        for i in 0..board.get_rows() {
+            for j in 0..board.get_cols() {
+                // A word starts after an empty square or the edge of the board
+                if search.letter_from((i, j), direction, -1).is_some() {
+                    continue;
+                }
+                let mut cells = vec![(i, j)];
+                while let Some(cell) =
+                    Board::get_pos_from_direction(i, j, &direction, cells.len() as i32)
+                        .filter(|&(x, y)| x < board.get_rows() && y < board.get_cols())
+                {
+                    cells.push(cell);
+                    let empty = cells
+                        .iter()
+                        .filter(|&&cell| letter_at(board, cell).is_none())
+                        .count();
+                    if empty > rack.len() {
+                        break;
+                    }
+                    // The word would go on with the letter after it
+                    if empty == 0 || search.letter_from(cell, direction, 1).is_some() {
+                        continue;
+                    }
+                    let connected = if empty_board {
+                        cells.contains(&center)
+                    } else {
+                        cells.iter().any(|&cell| {
+                            letter_at(board, cell).is_some()
+                                || search.letter_from(cell, cross_direction, -1).is_some()
+                                || search.letter_from(cell, cross_direction, 1).is_some()
+                        })
+                    };
+                    if connected {
+                        search.place(
+                            &cells,
+                            &mut String::new(),
+                            &mut rack,
+                            &mut Vec::new(),
+                            &mut moves,
+                        );
+                    }
+                }
+            }
+        }
+    }
+    // The same word at the same place can be played with or without a blank, keep the best
+    moves.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.word.cmp(&b.word))
+            .then_with(|| a.position.start().cmp(&b.position.start()))
+            .then_with(|| a.position.end().cmp(&b.position.end()))
+    });
+    let mut best: Vec<Move> = Vec::new();
+    for candidate in moves {
+        if !best
+            .iter()
+            .any(|kept| kept.word == candidate.word && kept.position == candidate.position)
+        {
+            best.push(candidate);
+        }
+        if best.len() == count {
+            break;
+        }
+    }
+    best
+}
+
+// The letter on a square, None if it is empty
+fn letter_at(board: &Board, (i, j): (usize, usize)) -> Option<char> {
+    board
+        .letters
+        .get(i)?
+        .get(j)
+        .filter(|&&c| c != EMPTY_SQUARE && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+}
+
+impl MoveSearch<'_> {
+    fn letter_from(&self, (i, j): (usize, usize), direction: Direction, step: i32) -> Option<char> {
+        letter_at(
+            self.board,
+            Board::get_pos_from_direction(i, j, &direction, step)?,
+        )
+    }
+    // The word across the line through `cell` once `letter` is put there.
+    // None when the letter has no neighbour across the line
+    fn cross_word(&self, cell: (usize, usize), letter: char) -> Option<String> {
+        let mut before = 0;
+        while self
+            .letter_from(cell, self.cross_direction, -(before + 1))
+            .is_some()
+        {
+            before += 1;
+        }
+        let mut after = 0;
+        while self
+            .letter_from(cell, self.cross_direction, after + 1)
+            .is_some()
+        {
+            after += 1;
+        }
+        if before == 0 && after == 0 {
+            return None;
+        }
+        let word = (-before..=after)
+            .map(|step| {
+                if step == 0 {
+                    letter
+                } else {
+                    self.letter_from(cell, self.cross_direction, step)
+                        .expect("the cross word is made of letters")
+                }
+            })
+            .collect();
+        Some(word)
+    }
+    // Fill the empty squares of `cells` with the rack, following the prefixes of the dictionary
+    fn place(
+        &self,
+        cells: &[(usize, usize)],
+        word: &mut String,
+        rack: &mut Vec<char>,
+        tiles: &mut Vec<PlacedTile>,
+        moves: &mut Vec<Move>,
+    ) {
+        let Some(&cell) = cells.get(word.chars().count()) else {
+            if self.dictionary.search(word) {
+                moves.push(self.to_move(cells, word, tiles));
+            }
+            return;
+        };
+        if let Some(letter) = letter_at(self.board, cell) {
+            word.push(letter);
+            if self.dictionary.starts_with(word) {
+                self.place(cells, word, rack, tiles, moves);
+            }
+            word.pop();
+            return;
+        }
+        let mut tried = Vec::new();
+        for index in 0..rack.len() {
+            let tile = rack[index];
+            if tried.contains(&tile) {
+                continue;
+            }
+            tried.push(tile);
+            let letters: Vec<char> = if tile == BLANK_TILE {
+                ('A'..='Z').collect()
+            } else {
+                vec![tile]
+            };
+            rack.swap_remove(index);
+            for letter in letters {
+                word.push(letter);
+                let cross_word_valid = self
+                    .cross_word(cell, letter)
+                    .is_none_or(|cross_word| self.dictionary.search(&cross_word));
+                if cross_word_valid && self.dictionary.starts_with(word) {
+                    tiles.push(PlacedTile {
+                        cell,
+                        letter,
+                        blank: tile == BLANK_TILE,
+                    });
+                    self.place(cells, word, rack, tiles, moves);
+                    tiles.pop();
+                }
+                word.pop();
+            }
+            // Put the tile back where it was
+            rack.push(tile);
+            let last = rack.len() - 1;
+            rack.swap(index, last);
+        }
+    }
+    // The move for the tiles put down on `cells`. Premiums only count for these tiles
+    fn to_move(&self, cells: &[(usize, usize)], word: &str, tiles: &[PlacedTile]) -> Move {
+        let tile_at = |cell: (usize, usize)| tiles.iter().find(|tile| tile.cell == cell);
+        let tile_score = |tile: &PlacedTile| {
+            let premium = self.layout.get(tile.cell.0, tile.cell.1);
+            let points = if tile.blank {
+                0
+            } else {
+                letter_score(tile.letter)
+            };
+            (
+                points * premium.letter_multiplier(),
+                premium.word_multiplier(),
+            )
+        };
+        let mut word_score = 0;
+        let mut word_multiplier = 1;
+        for (&cell, letter) in cells.iter().zip(word.chars()) {
+            match tile_at(cell) {
+                Some(tile) => {
+                    let (points, multiplier) = tile_score(tile);
+                    word_score += points;
+                    word_multiplier *= multiplier;
+                }
+                None => word_score += letter_score(letter),
+            }
+        }
+        let mut score = word_score * word_multiplier;
+        let mut cross_words = Vec::new();
+        for tile in tiles {
+            if let Some(cross_word) = self.cross_word(tile.cell, tile.letter) {
+                let (points, multiplier) = tile_score(tile);
+                let others: u32 =
+                    cross_word.chars().map(letter_score).sum::<u32>() - letter_score(tile.letter);
+                score += (others + points) * multiplier;
+                cross_words.push(cross_word);
+            }
+        }
+        if tiles.len() == RACK_SIZE {
+            score += BINGO_BONUS;
+        }
+        Move {
+            word: word.to_string(),
+            position: WordPosition::new(cells[0], cells[cells.len() - 1]),
+            direction: self.direction,
+            tiles: tiles.to_vec(),
+            cross_words,
+            score,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::grid;
+    #[test]
+    fn test_cross_words_and_premiums() {
+        let board = grid(&["AT.", "..."]);
+        let dictionary = Trie::from(&vec!["AN", "AT", "ON", "TO"]);
+        let layout = PremiumLayout::parse("...\n.d.").unwrap();
+        let moves = best_moves(&board, "ON", &layout, &dictionary, 10);
+        let found: Vec<_> = moves
+            .iter()
+            .map(|found| (found.word.as_str(), found.direction, found.score))
+            .collect();
+        // ON at (1, 0) would make AO down the first column
+        assert_eq!(
+            found,
+            vec![
+                ("ON", Direction::Right, 6),
+                ("TO", Direction::Down, 3),
+                ("AN", Direction::Down, 2),
+            ]
+        );
+        assert_eq!(moves[0].position, WordPosition::new((1, 1), (1, 2)));
+        assert_eq!(moves[0].cross_words, vec!["TO"]);
+    }
+    #[test]
+    fn test_first_move_and_bingo() {
+        let board = grid(&[".....", ".....", "....."]);
+        let dictionary = Trie::from(&vec!["CAT", "SEVENTY"]);
+        let moves = best_moves(&board, "TAC", &PremiumLayout::default(), &dictionary, 10);
+        // Across or down, CAT has to cover the center square (1, 2)
+        assert_eq!(moves.len(), 4);
+        assert!(best_moves(&board, "TAC", &PremiumLayout::default(), &dictionary, 0).is_empty());
+        assert!(moves
+            .iter()
+            .all(|found| found.position.cells().contains(&(1, 2))));
+        let board = grid(&["........."]);
+        let moves = best_moves(&board, "SEVEN?Y", &PremiumLayout::default(), &dictionary, 1);
+        // The blank is the T and scores nothing
+        assert_eq!(moves[0].score, 1 + 1 + 4 + 1 + 1 + 4 + BINGO_BONUS);
+        assert!(moves[0]
+            .tiles
+            .iter()
+            .any(|tile| tile.blank && tile.letter == 'T'));
+    }
+}
@@ -0,0 +1,111 @@
+use crate::api::{handle, too_large, ApiError, ApiResponse, MAX_BODY_BYTES};
+use std::io::{self, Read};
+use std::net::{Ipv4Addr, SocketAddr};
+use tiny_http::{Header, Request, Response, Server};
+
+/// Start a server on localhost only, port 0 picks a free port
+pub fn bind(port: u16) -> Result<Server, Box<dyn std::error::Error + Send + Sync>> {
+    Server::http(SocketAddr::from((Ipv4Addr::LOCALHOST, port)))
+}
+
+/// Answer requests one at a time, until the server is unblocked
+pub fn serve(server: &Server) {
+    for request in server.incoming_requests() {
+        if let Err(e) = answer(request) {
+            eprintln!("Could not answer a request: {}", e);
+        }
+    }
+}
+
+fn answer(mut request: Request) -> io::Result<()> {
+    // The endpoints take no query parameters, `/solve?x=1` is still `/solve`
+    let path = request
+        .url()
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let response = match read_body(&mut request) {
+        Ok(body) => handle(request.method().as_str(), &path, &body),
+        Err(error) => ApiResponse {
+            status: error.status,
+            body: error.to_json(),
+        },
+    };
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("the header is valid ASCII");
+    request.respond(
+        Response::from_string(response.body)
+            .with_status_code(response.status)
+            .with_header(content_type),
+    )
+}
+
+// Read the body, at most one byte past the limit so that a large body is not kept in memory
+fn read_body(request: &mut Request) -> Result<Vec<u8>, ApiError> {
+    if request
+        .body_length()
+        .is_some_and(|length| length > MAX_BODY_BYTES)
+    {
+        return Err(too_large());
+    }
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| ApiError::new(400, "invalid_body", e.to_string()))?;
+    if body.len() > MAX_BODY_BYTES {
+        return Err(too_large());
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpStream;
+    use std::sync::Arc;
+    use std::thread;
+
+    // Send a raw HTTP request like any local client would, and return the status line and body
+    fn post(port: u16, path: &str, body: &str) -> (String, String) {
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.lines().next().unwrap().to_string(), body.to_string())
+    }
+
+    #[test]
+    fn test_local_client() {
+        let server = Arc::new(bind(0).unwrap());
+        let port = server.server_addr().to_ip().unwrap().port();
+        let handle = {
+            let server = Arc::clone(&server);
+            thread::spawn(move || serve(&server))
+        };
+        let (status, body) = post(port, "/solve", r#"{"grid": ["CAT"], "words": ["tac"]}"#);
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert_eq!(
+            body,
+            r#"{"matches":[{"word":"TAC","start":[0,2],"end":[0,0]}],"missing":[]}"#
+        );
+        let (status, _) = post(port, "/solve?x=1", r#"{"grid": ["CAT"], "words": []}"#);
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        let (status, body) = post(port, "/solve", &" ".repeat(MAX_BODY_BYTES + 1));
+        assert!(status.starts_with("HTTP/1.1 413"));
+        assert!(body.contains("too_large"));
+        server.unblock();
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,200 @@
+use crate::board::{Board, WordPosition};
+use crate::state::search_state::SearchState;
+use crate::stats::{SearchStats, Stopwatch};
+use crate::trie::Trie;
+
+/// A target word that the search did not find in the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingWord {
+    pub word: String,
+    /// The longest prefix of the word that can be read in the board, empty if none
+    pub prefix: String,
+    /// Where the longest prefix was found
+    pub position: Option<WordPosition>,
+}
+
+/// A puzzle together with the result of solving it
+pub struct SolvedPuzzle {
+    pub board: Board,
+    pub target_words: Vec<String>,
+    pub found: Vec<WordPosition>,
+    pub missing: Vec<MissingWord>,
+}
+impl SolvedPuzzle {
+    /// Solve a board for the given target words
+    pub fn new(board: Board, target_words: Vec<String>) -> Self {
+        let target_words_str = target_words.iter().map(String::as_str).collect();
+        let found = solve(&board, &Trie::from(&target_words_str));
+        let missing = find_missing_words(&board, &target_words, &found);
+        SolvedPuzzle {
+            board,
+            target_words,
+            found,
+            missing,
+        }
+    }
+}
+
+/// Solve several puzzles, keeping their order
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::solver::solve_batch;
+/// let puzzles = vec![
+///     (Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]), vec!["ab".to_string()]),
+///     (Board::new(&vec![vec!['e', 'f'], vec!['g', 'h']]), vec!["he".to_string(), "xy".to_string()]),
+/// ];
+/// let solved = solve_batch(puzzles);
+/// assert_eq!(solved[0].found.len(), 1);
+/// assert_eq!(solved[1].found.len(), 1);
+/// assert_eq!(solved[1].missing[0].word, "xy");
+/// ```
+pub fn solve_batch(puzzles: Vec<(Board, Vec<String>)>) -> Vec<SolvedPuzzle> {
+    puzzles
+        .into_iter()
+        .map(|(board, target_words)| SolvedPuzzle::new(board, target_words))
+        .collect()
+}
+
+/// Walk every search state of the board and return the position of each word found
+/// # Arguments
+/// * `board` - The board to search
+/// * `trie` - The trie built from the target words
+/// # Returns
+/// * `Vec<WordPosition>` - The found words, in the order the search reached them
+/// # Example
+/// ```
+/// use word_search_solver::board::{Board, WordPosition};
+/// use word_search_solver::solver::solve;
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+/// let trie = Trie::from(&vec!["abc", "hc"]);
+/// assert_eq!(solve(&board, &trie), vec![WordPosition::new((0, 0), (0, 2))]);
+/// ```
+pub fn solve(board: &Board, trie: &Trie) -> Vec<WordPosition> {
+    let mut state = SearchState::new();
+    let mut found = Vec::new();
+    loop {
+        found.extend(board.check_state(&mut state, trie));
+        match board.next_state(&state, state.feasible) {
+            Some(next_state) => state = next_state,
+            None => break,
+        }
+    }
+    found
+}
+
+/// Same as `solve`, also counting the work done by the search. Counting costs a few
+/// allocations per state, `solve` is the one to use when the statistics are not needed
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::solver::solve_with_stats;
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+/// let (found, stats) = solve_with_stats(&board, &Trie::from(&vec!["abc"]));
+/// assert_eq!(stats.words_found, found.len());
+/// assert_eq!(stats.max_depth, 3);
+/// ```
+pub fn solve_with_stats(board: &Board, trie: &Trie) -> (Vec<WordPosition>, SearchStats) {
+    let stopwatch = Stopwatch::start();
+    let mut state = SearchState::new();
+    let mut found = Vec::new();
+    let mut stats = SearchStats::default();
+    loop {
+        let word_position = board.check_state(&mut state, trie);
+        stats.record(board, &state, word_position.is_some());
+        found.extend(word_position);
+        match board.next_state(&state, state.feasible) {
+            Some(next_state) => state = next_state,
+            None => break,
+        }
+    }
+    stats.elapsed = stopwatch.elapsed();
+    (found, stats)
+}
+
+/// Return the target words that are not covered by any of the found positions,
+/// together with the longest prefix of each that can be read in the board
+/// # Arguments
+/// * `board` - The searched board
+/// * `target_words` - The words that should be in the board
+/// * `found` - The positions returned by the search
+/// # Example
+/// ```
+/// use word_search_solver::board::{Board, WordPosition};
+/// use word_search_solver::solver::{find_missing_words, MissingWord};
+/// let board = Board::new(&vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h', 'i']]);
+/// let found = vec![WordPosition::new((0, 0), (0, 2))];
+/// let missing = find_missing_words(&board, &["abc".to_string(), "hex".to_string()], &found);
+/// assert_eq!(
+///     missing,
+///     vec![MissingWord {
+///         word: "hex".to_string(),
+///         prefix: "he".to_string(),
+///         position: Some(WordPosition::new((2, 1), (1, 1))),
+///     }]
+/// );
+/// ```
+pub fn find_missing_words(
+    board: &Board,
+    target_words: &[String],
+    found: &[WordPosition],
+) -> Vec<MissingWord> {
+    let found_words: Vec<String> = found.iter().map(|pos| board.get_word(pos)).collect();
+    let mut missing = Vec::new();
+    for word in target_words.iter().map(|word| word.trim()) {
+        if word.is_empty() || found_words.iter().any(|found_word| found_word == word) {
+            continue;
+        }
+        let (prefix, position) = match board.longest_prefix(word) {
+            Some((prefix, position)) => (prefix, Some(position)),
+            None => (String::new(), None),
+        };
+        missing.push(MissingWord {
+            word: word.to_owned(),
+            prefix,
+            position,
+        });
+    }
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::loader::{fetch_board, fetch_target_words};
+    #[test]
+    fn test_solve_finds_every_direction() {
+        let board = Board::new(&vec![
+            vec!['a', 'b', 'c'],
+            vec!['d', 'e', 'f'],
+            vec!['g', 'h', 'i'],
+        ]);
+        let trie = Trie::from(&vec!["cba", "beh", "ie"]);
+        let found: Vec<String> = solve(&board, &trie)
+            .iter()
+            .map(|pos| board.get_word(pos))
+            .collect();
+        assert_eq!(found, vec!["beh", "cba", "ie"]);
+    }
+    #[test]
+    fn test_missing_words_in_sample_puzzle() {
+        let board = Board::new(&fetch_board(Path::new("src/input/board.txt")).unwrap());
+        let target_words = fetch_target_words(Path::new("src/input/words.txt")).unwrap();
+        let target_words_str = target_words.iter().map(String::as_str).collect();
+        let found = solve(&board, &Trie::from(&target_words_str));
+        let missing = find_missing_words(&board, &target_words, &found);
+        let missing_words: Vec<&str> = missing.iter().map(|m| m.word.as_str()).collect();
+        assert_eq!(missing_words, vec!["JAVASCRIPT", "HASKELL"]);
+        for m in &missing {
+            assert!(m.word.starts_with(&m.prefix));
+            assert_ne!(m.prefix, m.word);
+            if let Some(position) = &m.position {
+                assert_eq!(board.get_word(position), m.prefix);
+            }
+        }
+    }
+}
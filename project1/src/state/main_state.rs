@@ -1,141 +1,842 @@
-use crate::board::Board;
-use crate::constant::*;
+use crate::board::{Board, Direction, WordPosition};
+use crate::export::DEFAULT_FONT;
+use crate::heatmap::Heatmap;
+use crate::layout::Layout;
+use crate::loader::{is_board_file, load_puzzle};
+use crate::message::{hidden_message, leftover_cells};
+use crate::solver::{find_missing_words, MissingWord};
+use crate::state::play_state::PlayState;
 use crate::state::search_state::SearchState;
+use crate::stats::{exhaustive_prefixes, SearchStats};
+use crate::theme::Theme;
+use crate::trace::TraceEntry;
 use crate::trie::Trie;
 use crate::utils::*;
-use ggez::event::EventHandler;
+use ggez::event::{ErrorOrigin, EventHandler};
 use ggez::graphics::{self};
-use ggez::{glam::*, Context, GameResult};
-use std::path::Path;
+use ggez::input::keyboard::{KeyCode, KeyInput};
+use ggez::input::mouse::MouseButton;
+use ggez::{glam::*, Context, GameError, GameResult};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const DEFAULT_STEPS_PER_SECOND: u32 = 100;
+const MAX_STEPS_PER_SECOND: u32 = 3200;
+
+/// Everything needed to undo one step of the search
+pub struct Snapshot {
+    pub search_state: SearchState,
+    pub found_count: usize,
+    pub current_idx: Option<WordPosition>,
+    pub stats: SearchStats,
+    pub visited: Option<WordPosition>, // Line the step checked, counted in the visit heatmap
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Visualize, // Watch the solver search the board
+    Play,      // Let the player find the words
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeatmapMode {
+    Off,
+    Visits, // How many search states touched each cell
+    Words,  // How many found words pass through each cell
+}
+impl HeatmapMode {
+    pub fn next(self) -> HeatmapMode {
+        match self {
+            HeatmapMode::Off => HeatmapMode::Visits,
+            HeatmapMode::Visits => HeatmapMode::Words,
+            HeatmapMode::Words => HeatmapMode::Off,
+        }
+    }
+}
 
 pub struct MainState {
     pub grid_mesh: graphics::Mesh,
-    pub line_mesh: graphics::Mesh,
+    pub board_image: graphics::Image, // Grid and letters, drawn again only when the layout changes
+    pub line_mesh: graphics::Mesh,    // Strokes of the found words, built from `mb`
+    pub line_mesh_words: usize,       // Number of found words already added to `mb`
+    pub line_mesh_generation: usize,  // Value of `strokes_generation` when `mb` was started
+    pub strokes_generation: usize,    // Bumped whenever found words are removed or replaced
     pub board_state: Board,
     pub mb: graphics::MeshBuilder,
     pub trie: Trie,
-    pub found_words_idx: Vec<(usize, usize)>,
-    pub current_idx: (Vec2, Vec2), // Current line position to check if it is a word
+    pub target_words: Vec<String>,
+    pub found_words: Vec<WordPosition>,
+    pub missing_words: Option<Vec<MissingWord>>, // Set once the search is over
+    pub current_idx: Option<WordPosition>,       // Current line position to check if it is a word
     pub search_state: SearchState,
+    pub history: Vec<Snapshot>, // States already visited, used to step backward
+    pub paused: bool,
+    pub steps_per_second: u32,
+    pub mode: Mode,
+    pub play_state: PlayState,
+    pub board_path: PathBuf, // Files of the current puzzle, used to reload it
+    pub words_path: PathBuf,
+    pub layout: Layout, // Where the grid and panels are drawn for the current window size
+    pub word_list_scroll: f32, // Scroll offset of the word list on the right of the grid
+    pub missing_scroll: usize, // First entry shown in the missing words panel on the left
+    pub hovered_word: Option<usize>, // Found word under the mouse, on the grid or in the word list
+    pub themes: Vec<Theme>, // Themes the theme key cycles through
+    pub theme_idx: usize,
+    pub replay: Option<Vec<TraceEntry>>, // Recorded search states shown instead of solving
+    pub stats: SearchStats,
+    pub exhaustive_prefixes: usize, // Lines a search without pruning would check on this board
+    pub show_stats: bool,
+    pub heatmap_mode: HeatmapMode,
+    pub visit_heatmap: Heatmap, // Cells touched by the states visited so far
+    pub word_heatmap: Heatmap,  // Cells covered by the words found so far
+    pub show_leftovers: bool,   // Dim the found words to read the letters they leave
 }
 impl MainState {
-    pub fn new(ctx: &mut Context) -> GameResult<MainState> {
+    pub fn new(ctx: &mut Context, board_path: &Path, words_path: &Path) -> GameResult<MainState> {
         // Load board and target words
-        let board_file_path = Path::new("src/input/board.txt");
-        let target_words_file_path = Path::new("src/input/words.txt");
-        let letters: Vec<Vec<char>> = fetch_board(board_file_path);
-        if letters.len() != BOARD_SIZE || letters[0].len() != BOARD_SIZE {
-            panic!("Board size is not correct, please check the input file or modify the BOARD_SIZE constant");
-        }
+        let (board_state, target_words) = load_puzzle(board_path, words_path)
+            .map_err(|e| GameError::ResourceLoadError(e.to_string()))?;
         // Initialize grid
-        let grid_mesh = build_grid(ctx);
+        let (width, height) = to_logical(ctx, ctx.gfx.drawable_size());
+        let layout = Layout::new(
+            width,
+            height,
+            board_state.get_rows(),
+            board_state.get_cols(),
+        );
+        let themes = Theme::presets();
+        let grid_mesh = build_grid(ctx, &layout, &themes[0]);
+        ctx.gfx.add_font(
+            "Montserrat",
+            graphics::FontData::from_vec(DEFAULT_FONT.to_vec())?,
+        );
+        let board_image = build_board_image(ctx, &board_state, &grid_mesh, &layout, &themes[0])?;
 
         // Initialize mesh & mesh builder, for building persistent lines
         let mb = &mut graphics::MeshBuilder::new();
         let line_mesh = graphics::Mesh::from_data(ctx, mb.build());
 
-        let board_state = Board::new(&letters);
-
         // Initialize trie
-        let target_words: Vec<String> = fetch_target_words(target_words_file_path);
         let target_words_str = target_words.iter().map(String::as_str).collect();
         let trie = Trie::from(&target_words_str);
+        let play_state = PlayState::new(&board_state, &trie, &target_words);
+        let exhaustive_prefixes = exhaustive_prefixes(&board_state);
+        let visit_heatmap = Heatmap::new(board_state.get_rows(), board_state.get_cols());
+        let word_heatmap = visit_heatmap.clone();
         let s = MainState {
             // ...
             grid_mesh,
+            board_image,
             line_mesh,
+            line_mesh_words: 0,
+            line_mesh_generation: 0,
+            strokes_generation: 0,
             board_state,
             mb: graphics::MeshBuilder::new(),
             trie,
-            found_words_idx: Vec::new(),
-            current_idx: (Vec2::new(0.0, 0.0), Vec2::new(0.0, 0.0)),
+            target_words,
+            found_words: Vec::new(),
+            missing_words: None,
+            current_idx: None,
             search_state: SearchState::new(),
+            history: Vec::new(),
+            paused: false,
+            steps_per_second: DEFAULT_STEPS_PER_SECOND,
+            mode: Mode::Visualize,
+            play_state,
+            board_path: board_path.to_path_buf(),
+            words_path: words_path.to_path_buf(),
+            layout,
+            word_list_scroll: 0.0,
+            missing_scroll: 0,
+            hovered_word: None,
+            themes,
+            theme_idx: 0,
+            replay: None,
+            stats: SearchStats::default(),
+            exhaustive_prefixes,
+            show_stats: true,
+            heatmap_mode: HeatmapMode::Off,
+            visit_heatmap,
+            word_heatmap,
+            show_leftovers: false,
         };
-        ctx.gfx.add_font(
-            "Montserrat",
-            graphics::FontData::from_path(ctx, "/Montserrat-Bold.ttf")?,
-        );
         Ok(s)
     }
 }
 
+impl MainState {
+    /// Advance the search by one state, return true if a word was found
+    pub fn step(&mut self) -> bool {
+        if self.missing_words.is_some() {
+            return false;
+        }
+        if self.replay.is_some() {
+            return self.replay_step();
+        }
+        let visited = Heatmap::state_line(&self.board_state, &self.search_state);
+        if let Some(line) = &visited {
+            self.visit_heatmap.add_line(line);
+        }
+        self.history.push(Snapshot {
+            search_state: self.search_state.clone(),
+            found_count: self.found_words.len(),
+            current_idx: self.current_idx.clone(),
+            stats: self.stats,
+            visited,
+        });
+        if let Some(pos) = self.search_state.current_prefix() {
+            self.current_idx = Some(pos);
+        }
+        // Only the search itself is timed, as in `solve_with_stats`
+        let start = Instant::now();
+        let word_position = self
+            .board_state
+            .check_state(&mut self.search_state, &self.trie);
+        let found = word_position.is_some();
+        self.stats
+            .record(&self.board_state, &self.search_state, found);
+        let next_state = self
+            .board_state
+            .next_state(&self.search_state, self.search_state.feasible);
+        self.stats.elapsed += start.elapsed();
+        if let Some(word_position) = word_position {
+            println!("Found word: {:?}", self.search_state.current_prefix());
+            self.word_heatmap.add_line(&word_position);
+            self.found_words.push(word_position);
+        }
+        match next_state {
+            Some(state) => {
+                self.search_state = state;
+            }
+            None => self.finish_search(),
+        }
+        found
+    }
+    // Show the next recorded state of the replayed trace
+    fn replay_step(&mut self) -> bool {
+        let Some(trace) = &self.replay else {
+            return false;
+        };
+        let Some(entry) = trace.get(self.history.len()).cloned() else {
+            self.finish_search();
+            return false;
+        };
+        let trace_len = trace.len();
+        let visited = Heatmap::state_line(&self.board_state, &entry.search_state());
+        if let Some(line) = &visited {
+            self.visit_heatmap.add_line(line);
+        }
+        self.history.push(Snapshot {
+            search_state: self.search_state.clone(),
+            found_count: self.found_words.len(),
+            current_idx: self.current_idx.clone(),
+            stats: self.stats,
+            visited,
+        });
+        self.search_state = entry.search_state();
+        if let Some(pos) = self.search_state.current_prefix() {
+            self.current_idx = Some(pos);
+        }
+        let found = entry.found_position();
+        if let (Some(word), Some(word_position)) = (&entry.found, &found) {
+            println!("Replayed word: {} at step {}", word, entry.step);
+            self.word_heatmap.add_line(word_position);
+            self.found_words.push(word_position.clone());
+        }
+        self.stats
+            .record(&self.board_state, &self.search_state, found.is_some());
+        if self.history.len() == trace_len {
+            self.finish_search();
+        }
+        found.is_some()
+    }
+    // The search is over, list the target words that were not found
+    fn finish_search(&mut self) {
+        let missing_words =
+            find_missing_words(&self.board_state, &self.target_words, &self.found_words);
+        for missing_word in &missing_words {
+            println!("Missing word: {}", describe_missing_word(missing_word));
+        }
+        self.missing_words = Some(missing_words);
+        self.missing_scroll = 0;
+    }
+    /// Replay a recorded trace instead of solving, the trace must fit the current board
+    pub fn set_replay(&mut self, trace: Vec<TraceEntry>) -> GameResult {
+        let (rows, cols) = (self.board_state.get_rows(), self.board_state.get_cols());
+        let in_board = |(i, j): (usize, usize)| i < rows && j < cols;
+        if let Some(entry) = trace.iter().find(|entry| {
+            !in_board(entry.position)
+                || entry
+                    .found_position()
+                    .is_some_and(|word_position| !in_board(word_position.end()))
+        }) {
+            return Err(GameError::ResourceLoadError(format!(
+                "step {} of the trace is outside of the {}x{} board",
+                entry.step, rows, cols
+            )));
+        }
+        self.replay = Some(trace);
+        self.restart();
+        Ok(())
+    }
+    /// Undo the last step by restoring the recorded state
+    pub fn step_back(&mut self) {
+        if let Some(snapshot) = self.history.pop() {
+            self.search_state = snapshot.search_state;
+            if snapshot.found_count < self.found_words.len() {
+                for word_position in &self.found_words[snapshot.found_count..] {
+                    self.word_heatmap.remove_line(word_position);
+                }
+                self.found_words.truncate(snapshot.found_count);
+                self.clear_strokes();
+            }
+            self.current_idx = snapshot.current_idx;
+            self.stats = snapshot.stats;
+            if let Some(line) = &snapshot.visited {
+                self.visit_heatmap.remove_line(line);
+            }
+            self.missing_words = None;
+        }
+    }
+    /// Keep stepping until the next word is found or the search is over
+    pub fn next_match(&mut self) {
+        while self.missing_words.is_none() && !self.step() {}
+    }
+    /// Also search along these steps after the 8 directions, then start the search and the game again
+    pub fn set_steps(&mut self, steps: Vec<Direction>) {
+        self.board_state.set_steps(steps);
+        self.exhaustive_prefixes = exhaustive_prefixes(&self.board_state);
+        // A trace recorded without the steps does not match the new search
+        self.replay = None;
+        self.restart();
+        self.new_game();
+    }
+    /// Start the search again from the first state
+    pub fn restart(&mut self) {
+        self.search_state = SearchState::new();
+        self.found_words.clear();
+        self.missing_words = None;
+        self.history.clear();
+        self.stats = SearchStats::default();
+        self.visit_heatmap = Heatmap::new(self.board_state.get_rows(), self.board_state.get_cols());
+        self.word_heatmap = self.visit_heatmap.clone();
+        self.current_idx = None;
+        self.paused = false;
+        self.clear_strokes();
+    }
+    /// Replace the current puzzle, the current one is kept if the files cannot be loaded
+    pub fn load_puzzle(
+        &mut self,
+        ctx: &mut Context,
+        board_path: &Path,
+        words_path: &Path,
+    ) -> GameResult {
+        let (mut board_state, target_words) = load_puzzle(board_path, words_path)
+            .map_err(|e| GameError::ResourceLoadError(e.to_string()))?;
+        board_state.set_steps(self.board_state.steps().to_vec());
+        let target_words_str = target_words.iter().map(String::as_str).collect();
+        self.trie = Trie::from(&target_words_str);
+        self.layout = Layout::new(
+            self.layout.width,
+            self.layout.height,
+            board_state.get_rows(),
+            board_state.get_cols(),
+        );
+        self.board_state = board_state;
+        self.exhaustive_prefixes = exhaustive_prefixes(&self.board_state);
+        self.rebuild_board(ctx)?;
+        // A trace recorded on another board cannot be replayed
+        if let Some(trace) = self.replay.take() {
+            if let Err(e) = self.set_replay(trace) {
+                println!("Stopped replaying the trace: {}", e);
+            }
+        }
+        self.word_list_scroll = 0.0;
+        self.target_words = target_words;
+        self.board_path = board_path.to_path_buf();
+        self.words_path = words_path.to_path_buf();
+        self.restart();
+        self.new_game();
+        println!(
+            "Loaded puzzle {} with words {}",
+            board_path.display(),
+            words_path.display()
+        );
+        Ok(())
+    }
+    /// Read the current puzzle files again
+    pub fn reload(&mut self, ctx: &mut Context) -> GameResult {
+        let (board_path, words_path) = (self.board_path.clone(), self.words_path.clone());
+        self.load_puzzle(ctx, &board_path, &words_path)
+    }
+    /// Load a file dropped on the window, as the board or as the word list depending on its content
+    pub fn file_dropped_event(&mut self, ctx: &mut Context, path: PathBuf) -> GameResult {
+        let result = if is_board_file(&path) {
+            let words_path = self.words_path.clone();
+            self.load_puzzle(ctx, &path, &words_path)
+        } else {
+            let board_path = self.board_path.clone();
+            self.load_puzzle(ctx, &board_path, &path)
+        };
+        if let Err(e) = result {
+            println!("Could not load dropped file: {}", e);
+        }
+        Ok(())
+    }
+    /// Switch between watching the solver and playing
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            Mode::Visualize => Mode::Play,
+            Mode::Play => Mode::Visualize,
+        };
+        if self.mode == Mode::Play && self.play_state.is_over() {
+            self.new_game();
+        }
+        self.clear_strokes();
+    }
+    /// Draw the grid and the letters again, after the board or the layout changed
+    pub fn rebuild_board(&mut self, ctx: &mut Context) -> GameResult {
+        let theme = &self.themes[self.theme_idx];
+        self.grid_mesh = build_grid(ctx, &self.layout, theme);
+        self.board_image =
+            build_board_image(ctx, &self.board_state, &self.grid_mesh, &self.layout, theme)?;
+        self.clear_strokes();
+        Ok(())
+    }
+    /// Colors currently used to draw
+    pub fn theme(&self) -> &Theme {
+        &self.themes[self.theme_idx]
+    }
+    /// Switch to the next theme
+    pub fn next_theme(&mut self, ctx: &mut Context) -> GameResult {
+        self.theme_idx = (self.theme_idx + 1) % self.themes.len();
+        println!("Theme: {}", self.theme().name);
+        self.rebuild_board(ctx)
+    }
+    /// Add a theme, loaded from a file for example, and switch to it
+    pub fn add_theme(&mut self, ctx: &mut Context, theme: Theme) -> GameResult {
+        self.themes.push(theme);
+        self.theme_idx = self.themes.len() - 1;
+        self.rebuild_board(ctx)
+    }
+    // Forget the cached strokes, they are built again on the next frame
+    fn clear_strokes(&mut self) {
+        self.strokes_generation += 1;
+    }
+    // Add the strokes of the words found since the last frame to the cached mesh
+    fn update_line_mesh(&mut self, ctx: &mut Context) -> GameResult {
+        let words = match self.mode {
+            Mode::Visualize => &self.found_words,
+            Mode::Play => &self.play_state.found,
+        };
+        let theme = &self.themes[self.theme_idx];
+        // Words were removed or replaced since the strokes were cached
+        if self.line_mesh_generation != self.strokes_generation {
+            self.mb = graphics::MeshBuilder::new();
+            self.line_mesh_words = 0;
+            self.line_mesh_generation = self.strokes_generation;
+        }
+        if words.len() == self.line_mesh_words {
+            return Ok(());
+        }
+        for (idx, word_position) in words.iter().enumerate().skip(self.line_mesh_words) {
+            add_stroke(
+                &mut self.mb,
+                &self.layout,
+                word_position,
+                theme.word_color(idx, 0.35),
+            )?;
+        }
+        self.line_mesh = graphics::Mesh::from_data(ctx, self.mb.build());
+        self.line_mesh_words = words.len();
+        Ok(())
+    }
+    /// Start a new game with every target word left to find
+    pub fn new_game(&mut self) {
+        self.play_state = PlayState::new(&self.board_state, &self.trie, &self.target_words);
+        self.clear_strokes();
+    }
+    fn cell_at(&self, x: f32, y: f32) -> Option<(usize, usize)> {
+        self.layout.cell_at(x, y)
+    }
+    // Number of entries in the word list on the right of the grid
+    fn word_list_len(&self) -> usize {
+        match self.mode {
+            Mode::Visualize => self.found_words.len(),
+            Mode::Play => self.play_state.remaining_words.len(),
+        }
+    }
+    // Found word under a point of the window, either its entry in the word list or one of its cells
+    fn found_word_at(&self, x: f32, y: f32) -> Option<usize> {
+        if self.mode != Mode::Visualize {
+            return None;
+        }
+        if let Some(idx) = self.layout.word_entry_at(x, y, self.word_list_scroll) {
+            return Some(idx).filter(|&idx| idx < self.found_words.len());
+        }
+        let cell = self.cell_at(x, y)?;
+        // The last found word is drawn on top, so it wins when words cross
+        self.found_words
+            .iter()
+            .rposition(|word_position| word_position.cells().contains(&cell))
+    }
+    fn snapped_selection(&self) -> Option<WordPosition> {
+        let (start, end) = self.play_state.selection?;
+        self.board_state.snap_selection(start, end)
+    }
+    // Counts shown by the heatmap overlay, if it is on
+    fn heatmap(&self) -> Option<&Heatmap> {
+        match self.heatmap_mode {
+            HeatmapMode::Off => None,
+            HeatmapMode::Visits => Some(&self.visit_heatmap),
+            HeatmapMode::Words => Some(&self.word_heatmap),
+        }
+    }
+    // Search counters, with how many lines pruning saved compared to an exhaustive search
+    fn stats_text(&self) -> String {
+        let savings = self.stats.pruning_savings(self.exhaustive_prefixes);
+        format!(
+            "{}\nPruning skipped {:.1}% of the {} lines of an exhaustive search",
+            self.stats,
+            savings * 100.0,
+            self.exhaustive_prefixes
+        )
+    }
+    fn status(&self) -> String {
+        if self.mode == Mode::Play {
+            let progress = if self.play_state.is_over() {
+                "All words found!".to_string()
+            } else {
+                format!("{} words left", self.play_state.remaining_words.len())
+            };
+            return format!(
+                "Score: {}  Time: {}s  {} - Drag to select  H: hint  R: new game  G: watch solver  T: theme  F5: reload",
+                self.play_state.score,
+                self.play_state.elapsed.as_secs(),
+                progress
+            );
+        }
+        let progress = if self.missing_words.is_some() {
+            "Done".to_string()
+        } else if self.paused {
+            "Paused".to_string()
+        } else {
+            format!("Running at {} steps/s", self.steps_per_second)
+        };
+        let progress = match &self.replay {
+            Some(trace) => format!(
+                "Replaying step {}/{}, {}",
+                self.history.len(),
+                trace.len(),
+                progress
+            ),
+            None => progress,
+        };
+        let progress = match self.heatmap_mode {
+            HeatmapMode::Off => progress,
+            HeatmapMode::Visits => format!(
+                "{}, heatmap of visits (max {})",
+                progress,
+                self.visit_heatmap.max()
+            ),
+            HeatmapMode::Words => format!("{}, heatmap of found words", progress),
+        };
+        format!(
+            "{} - Space: pause  Right: step  Left: back  Up/Down: speed  N: next match  R: restart  S: stats  M: heatmap  L: leftovers  G: play  T: theme  F5: reload",
+            progress
+        )
+    }
+}
+
 impl EventHandler for MainState {
     fn update(&mut self, ctx: &mut Context) -> GameResult {
-        // Update code here...
-        const DESIRED_FPS: u32 = 100;
-        while ctx.time.check_update_time(DESIRED_FPS) {
-            if let Some(pos) = self.search_state.current_prefix() {
-                self.current_idx = pos.to_vec2();
-            }
-            if let Some(word_position) = self
-                .board_state
-                .check_state(&mut self.search_state, &self.trie)
-            {
-                println!("Found word: {:?}", self.search_state.current_prefix());
-                self.found_words_idx.push(word_position.to_1d(BOARD_SIZE));
-            }
-            match self
-                .board_state
-                .next_state(&self.search_state, self.search_state.feasible)
-            {
-                Some(state) => {
-                    self.search_state = state;
+        match self.mode {
+            Mode::Visualize => {
+                while ctx.time.check_update_time(self.steps_per_second) {
+                    if !self.paused {
+                        self.step();
+                    }
                 }
-                None => {
-                    // sleep
+            }
+            Mode::Play => self.play_state.tick(ctx.time.delta()),
+        }
+        Ok(())
+    }
+
+    fn resize_event(&mut self, ctx: &mut Context, width: f32, height: f32) -> GameResult {
+        let (width, height) = to_logical(ctx, (width, height));
+        self.layout = Layout::new(
+            width,
+            height,
+            self.board_state.get_rows(),
+            self.board_state.get_cols(),
+        );
+        self.rebuild_board(ctx)?;
+        self.word_list_scroll = self
+            .word_list_scroll
+            .min(self.layout.max_scroll(self.word_list_len()));
+        Ok(())
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, _x: f32, y: f32) -> GameResult {
+        let position = ctx.mouse.position();
+        let (x, _) = to_logical(ctx, (position.x, position.y));
+        // Over the missing words panel the wheel moves one entry at a time
+        if let Some(missing_words) = self
+            .missing_words
+            .as_ref()
+            .filter(|_| x < self.layout.start_x)
+        {
+            let last = missing_words.len().saturating_sub(1);
+            self.missing_scroll = if y < 0.0 {
+                (self.missing_scroll + 1).min(last)
+            } else {
+                self.missing_scroll.saturating_sub(1)
+            };
+            return Ok(());
+        }
+        let max_scroll = self.layout.max_scroll(self.word_list_len());
+        self.word_list_scroll =
+            (self.word_list_scroll - y * self.layout.line_height()).clamp(0.0, max_scroll);
+        Ok(())
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> GameResult {
+        let (x, y) = to_logical(ctx, (x, y));
+        if self.mode == Mode::Play && button == MouseButton::Left {
+            self.play_state.selection = self.cell_at(x, y).map(|cell| (cell, cell));
+        }
+        Ok(())
+    }
 
-                    std::thread::sleep(std::time::Duration::from_secs(20));
-                    ctx.request_quit();
+    fn mouse_motion_event(
+        &mut self,
+        ctx: &mut Context,
+        x: f32,
+        y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> GameResult {
+        let (x, y) = to_logical(ctx, (x, y));
+        if let (Some((start, _)), Some(cell)) = (self.play_state.selection, self.cell_at(x, y)) {
+            self.play_state.selection = Some((start, cell));
+        }
+        self.hovered_word = self.found_word_at(x, y);
+        Ok(())
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        _x: f32,
+        _y: f32,
+    ) -> GameResult {
+        if self.mode == Mode::Play && button == MouseButton::Left {
+            if let Some(selection) = self.snapped_selection() {
+                if let Some(word) =
+                    self.play_state
+                        .submit(&self.board_state, &self.trie, &selection)
+                {
+                    println!("Player found word: {}", word);
+                }
+            }
+            self.play_state.selection = None;
+        }
+        Ok(())
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        input: KeyInput,
+        _repeated: bool,
+    ) -> GameResult {
+        match input.keycode {
+            Some(KeyCode::G) => {
+                self.toggle_mode();
+                return Ok(());
+            }
+            Some(KeyCode::T) => return self.next_theme(ctx),
+            Some(KeyCode::F5) => {
+                if let Err(e) = self.reload(ctx) {
+                    println!("Could not reload puzzle: {}", e);
+                }
+                return Ok(());
+            }
+            _ => (),
+        }
+        if self.mode == Mode::Play {
+            match input.keycode {
+                Some(KeyCode::H) => {
+                    self.play_state.give_hint(&self.board_state);
                 }
+                Some(KeyCode::R) => self.new_game(),
+                _ => (),
+            }
+            return Ok(());
+        }
+        match input.keycode {
+            Some(KeyCode::Space) => self.paused = !self.paused,
+            Some(KeyCode::Right) => {
+                self.paused = true;
+                self.step();
+            }
+            Some(KeyCode::Left) => {
+                self.paused = true;
+                self.step_back();
+            }
+            Some(KeyCode::Up) => {
+                self.steps_per_second = (self.steps_per_second * 2).min(MAX_STEPS_PER_SECOND)
             }
+            Some(KeyCode::Down) => self.steps_per_second = (self.steps_per_second / 2).max(1),
+            Some(KeyCode::N) => {
+                self.paused = true;
+                self.next_match();
+            }
+            Some(KeyCode::R) => self.restart(),
+            Some(KeyCode::S) => self.show_stats = !self.show_stats,
+            Some(KeyCode::M) => self.heatmap_mode = self.heatmap_mode.next(),
+            Some(KeyCode::L) => self.show_leftovers = !self.show_leftovers,
+            _ => (),
         }
         Ok(())
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult {
-        let mut canvas =
-            graphics::Canvas::from_frame(ctx, graphics::Color::from([1.0, 1.0, 1.0, 1.0]));
-        // Draw code here...
-        canvas.draw(&self.grid_mesh, graphics::DrawParam::new());
-        for i in 0..BOARD_SIZE {
-            for j in 0..BOARD_SIZE {
-                let text_dest = graphics::DrawParam::new()
-                    .dest(Vec2::new(
-                        START_X + GRID_SIZE * j as f32 + GRID_SIZE / 2.0,
-                        START_Y + GRID_SIZE * i as f32 + GRID_SIZE / 2.0,
-                    ))
-                    .color(graphics::Color::from([0.0, 0.0, 0.0, 1.0]))
-                    .offset(Vec2::new(0.5, 0.5));
-                canvas.draw(
-                    graphics::Text::new(self.board_state.letters[i][j])
-                        .set_scale(30.)
-                        .set_font("Montserrat"),
-                    text_dest,
-                );
-            }
-        }
-        let mut mb = self.mb.clone();
-        let found_words_idx = self.found_words_idx.clone();
-        // Construct the line mesh based on the found words
-        for (idx, word_idx) in found_words_idx.iter().enumerate() {
-            let start_idx = word_idx.0;
-            let end_idx = word_idx.1;
-            draw_line(self, ctx, start_idx, end_idx, &mut mb, &mut canvas)?;
-            let word = self
-                .board_state
-                .get_word_from_1d_position(start_idx, end_idx);
-            display_word(ctx, &mut canvas, &word, idx)
-        }
-        // Draw the currently checking line
-        draw_highlighted_line(
-            ctx,
-            self.current_idx.0,
-            self.current_idx.1,
-            &mut canvas,
-            self.search_state.feasible,
+        self.update_line_mesh(ctx)?;
+        let theme = &self.themes[self.theme_idx];
+        let mut canvas = graphics::Canvas::from_frame(ctx, theme.background);
+        let layout = self.layout;
+        // Lay out in logical pixels, so the panels keep their size on HiDPI screens
+        canvas.set_screen_coordinates(graphics::Rect::new(0.0, 0.0, layout.width, layout.height));
+        if self.mode == Mode::Visualize {
+            if let Some(heatmap) = self.heatmap() {
+                draw_heatmap(ctx, &mut canvas, heatmap, &layout, theme.probe_cell)?;
+            }
+        }
+        // Strokes go under the letters so they stay readable
+        if self.line_mesh_words > 0 {
+            canvas.draw(&self.line_mesh, graphics::DrawParam::new());
+        }
+        let hovered = self
+            .hovered_word
+            .and_then(|idx| Some((idx, self.found_words.get(idx)?)));
+        if let Some((idx, word_position)) = hovered {
+            // The hovered word and its entry in the list stand out
+            let mb = &mut graphics::MeshBuilder::new();
+            add_stroke(mb, &layout, word_position, theme.word_color(idx, 0.5))?;
+            canvas.draw(
+                &graphics::Mesh::from_data(ctx, mb.build()),
+                graphics::DrawParam::new(),
+            );
+            highlight_word_entry(
+                ctx,
+                &mut canvas,
+                idx,
+                &layout,
+                self.word_list_scroll,
+                theme.word_color(idx, 0.25),
+            )?;
+        }
+        // The image has one texel per physical pixel
+        let image_scale = layout.width / self.board_image.width() as f32;
+        canvas.draw(
+            &self.board_image,
+            graphics::DrawParam::new().scale(Vec2::splat(image_scale)),
         );
-        self.mb = mb;
+        match self.mode {
+            Mode::Visualize => {
+                if self.show_leftovers {
+                    let leftover = leftover_cells(&self.board_state, &self.found_words);
+                    draw_leftovers(
+                        ctx,
+                        &mut canvas,
+                        &self.board_state,
+                        &leftover,
+                        &layout,
+                        theme,
+                    )?;
+                }
+                let entries: Vec<(String, graphics::Color)> = self
+                    .found_words
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, word_position)| {
+                        let word = self.board_state.get_word(word_position);
+                        (word, theme.word_color(idx, 1.0))
+                    })
+                    .collect();
+                display_word_list(ctx, &mut canvas, &entries, &layout, self.word_list_scroll)?;
+                // Draw the currently checking line
+                if let Some(current_idx) = &self.current_idx {
+                    draw_highlighted_line(
+                        ctx,
+                        current_idx,
+                        &mut canvas,
+                        self.search_state.feasible,
+                        &layout,
+                        theme,
+                    );
+                }
+                if self.show_leftovers {
+                    let message = hidden_message(&self.board_state, &self.found_words);
+                    let text = format!("Leftover letters: {}", message);
+                    display_stats(ctx, &mut canvas, &text, &layout, theme);
+                } else if self.show_stats {
+                    display_stats(ctx, &mut canvas, &self.stats_text(), &layout, theme);
+                }
+                if let Some(missing_words) = &self.missing_words {
+                    let entries: Vec<String> =
+                        missing_words.iter().map(describe_missing_word).collect();
+                    display_missing_words(
+                        ctx,
+                        &mut canvas,
+                        &entries,
+                        self.missing_scroll,
+                        &layout,
+                        theme,
+                    )?;
+                }
+            }
+            Mode::Play => {
+                let entries: Vec<(String, graphics::Color)> = self
+                    .play_state
+                    .remaining_words
+                    .iter()
+                    .map(|word| (word.clone(), theme.text))
+                    .collect();
+                display_word_list(ctx, &mut canvas, &entries, &layout, self.word_list_scroll)?;
+                // Draw the current selection
+                if let Some(selection) = self.snapped_selection() {
+                    draw_highlighted_line(ctx, &selection, &mut canvas, true, &layout, theme);
+                }
+                if let Some(cell) = self.play_state.hint {
+                    let hint = WordPosition::new(cell, cell);
+                    draw_highlighted_line(ctx, &hint, &mut canvas, true, &layout, theme);
+                }
+            }
+        }
+        display_status(ctx, &mut canvas, &self.status(), &layout, theme);
         canvas.finish(ctx)?;
         Ok(())
     }
+
+    fn on_error(&mut self, _ctx: &mut Context, origin: ErrorOrigin, _e: GameError) -> bool {
+        // The event loop already reported the error, only a broken frame closes the window
+        matches!(origin, ErrorOrigin::Update | ErrorOrigin::Draw)
+    }
+}
+
+// Convert a size or a position in physical pixels, as winit reports them, to logical pixels
+fn to_logical(ctx: &Context, (x, y): (f32, f32)) -> (f32, f32) {
+    let scale_factor = ctx.gfx.window().scale_factor() as f32;
+    (x / scale_factor, y / scale_factor)
 }
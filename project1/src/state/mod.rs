@@ -1,2 +1,4 @@
+#[cfg(feature = "gui")]
 pub mod main_state;
+pub mod play_state;
 pub mod search_state;
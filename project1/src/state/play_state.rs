@@ -0,0 +1,165 @@
+use crate::board::{Board, WordPosition};
+use crate::solver::solve;
+use crate::trie::Trie;
+use std::time::Duration;
+
+const POINTS_PER_LETTER: u32 = 10;
+const HINT_PENALTY: u32 = 20;
+
+/// State of a game where the player looks for the words themselves
+pub struct PlayState {
+    pub remaining_words: Vec<String>,
+    pub found: Vec<WordPosition>,
+    pub score: u32,
+    pub elapsed: Duration,
+    pub selection: Option<((usize, usize), (usize, usize))>, // Cells under the mouse drag
+    pub hint: Option<(usize, usize)>,                        // First cell of the hinted word
+    solutions: Vec<WordPosition>,                            // Solver results used as hints
+}
+impl PlayState {
+    /// Start a game, only the target words that the solver can find are left to the player
+    pub fn new(board: &Board, trie: &Trie, target_words: &[String]) -> Self {
+        let solutions = solve(board, trie);
+        let solved_words: Vec<String> = solutions.iter().map(|pos| board.get_word(pos)).collect();
+        let mut remaining_words: Vec<String> = Vec::new();
+        for word in target_words.iter().map(|word| word.trim()) {
+            if solved_words.iter().any(|solved| solved == word)
+                && !remaining_words.iter().any(|remaining| remaining == word)
+            {
+                remaining_words.push(word.to_owned());
+            }
+        }
+        PlayState {
+            remaining_words,
+            found: Vec::new(),
+            score: 0,
+            elapsed: Duration::ZERO,
+            selection: None,
+            hint: None,
+            solutions,
+        }
+    }
+    pub fn is_over(&self) -> bool {
+        self.remaining_words.is_empty()
+    }
+    /// Advance the timer, it stops once every word is found
+    pub fn tick(&mut self, delta: Duration) {
+        if !self.is_over() {
+            self.elapsed += delta;
+        }
+    }
+    /// Check a selection against the trie, in both reading directions
+    /// # Returns
+    /// * `Option<String>` - The word if the selection is a word that has not been found yet
+    pub fn submit(
+        &mut self,
+        board: &Board,
+        trie: &Trie,
+        selection: &WordPosition,
+    ) -> Option<String> {
+        let forward = board.get_word(selection);
+        let backward: String = forward.chars().rev().collect();
+        let (word, position) = if self.is_remaining(trie, &forward) {
+            (forward, selection.clone())
+        } else if self.is_remaining(trie, &backward) {
+            (backward, selection.reversed())
+        } else {
+            // A word read along a step of the board skips cells of the selected line,
+            // it is found by selecting its first and last letters
+            let ends = [
+                (selection.start(), selection.end()),
+                (selection.end(), selection.start()),
+            ];
+            let solution = self.solutions.iter().find(|solution| {
+                ends.contains(&(solution.start(), solution.end()))
+                    && self.is_remaining(trie, &board.get_word(solution))
+            })?;
+            (board.get_word(solution), solution.clone())
+        };
+        self.remaining_words.retain(|remaining| *remaining != word);
+        if self.hint == Some(position.start()) {
+            self.hint = None;
+        }
+        self.score += POINTS_PER_LETTER * word.chars().count() as u32;
+        self.found.push(position);
+        Some(word)
+    }
+    /// Reveal the first cell of a word that is still to be found, at the cost of some points
+    pub fn give_hint(&mut self, board: &Board) -> Option<(usize, usize)> {
+        let solution = self
+            .solutions
+            .iter()
+            .find(|pos| self.remaining_words.contains(&board.get_word(pos)))?;
+        self.hint = Some(solution.start());
+        self.score = self.score.saturating_sub(HINT_PENALTY);
+        self.hint
+    }
+    fn is_remaining(&self, trie: &Trie, word: &str) -> bool {
+        trie.search(word)
+            && self
+                .remaining_words
+                .iter()
+                .any(|remaining| remaining == word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Direction;
+    use crate::test_utils::{grid, trie_of, words};
+    // "dog" cannot be read in the board
+    const BOARD: [&str; 3] = ["cat", "xox", "xxg"];
+    const WORDS: [&str; 3] = ["cat", "go", "dog"];
+    #[test]
+    fn test_unsolvable_words_are_not_required() {
+        let (board, trie) = (grid(&BOARD), trie_of(&WORDS));
+        let play_state = PlayState::new(&board, &trie, &words(&WORDS));
+        assert_eq!(play_state.remaining_words, vec!["cat", "go"]);
+    }
+    #[test]
+    fn test_submit_accepts_both_reading_directions() {
+        let (board, trie) = (grid(&BOARD), trie_of(&WORDS));
+        let mut play_state = PlayState::new(&board, &trie, &words(&WORDS));
+        let cat = WordPosition::new((0, 2), (0, 0));
+        assert_eq!(
+            play_state.submit(&board, &trie, &cat),
+            Some("cat".to_string())
+        );
+        assert_eq!(play_state.found, vec![WordPosition::new((0, 0), (0, 2))]);
+        assert_eq!(play_state.submit(&board, &trie, &cat), None);
+        let go = WordPosition::new((2, 2), (1, 1));
+        assert_eq!(
+            play_state.submit(&board, &trie, &go),
+            Some("go".to_string())
+        );
+        assert_eq!(play_state.score, 50);
+        assert!(play_state.is_over());
+    }
+    #[test]
+    fn test_submit_a_word_that_skips_letters() {
+        let mut board = Board::new(&vec![
+            vec!['d', 'x', 'o', 'x', 'g'],
+            vec!['x', 'x', 'x', 'x', 'x'],
+        ]);
+        board.set_steps(vec![Direction::Step(0, 2)]);
+        let words = vec!["dog".to_string()];
+        let trie = Trie::from(&vec!["dog"]);
+        let mut play_state = PlayState::new(&board, &trie, &words);
+        assert_eq!(play_state.remaining_words, words);
+        let selection = board.snap_selection((0, 4), (0, 0)).unwrap();
+        assert_eq!(
+            play_state.submit(&board, &trie, &selection),
+            Some("dog".to_string())
+        );
+        assert_eq!(play_state.found[0].cells(), vec![(0, 0), (0, 2), (0, 4)]);
+    }
+    #[test]
+    fn test_hint_points_to_a_remaining_word() {
+        let (board, trie) = (grid(&BOARD), trie_of(&WORDS));
+        let mut play_state = PlayState::new(&board, &trie, &words(&WORDS));
+        play_state.submit(&board, &trie, &WordPosition::new((0, 0), (0, 2)));
+        assert_eq!(play_state.give_hint(&board), Some((2, 2)));
+        assert_eq!(play_state.score, 10);
+    }
+}
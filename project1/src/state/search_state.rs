@@ -1,6 +1,6 @@
 use crate::board::*;
 use crate::constant::*;
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SearchState {
     pub position: (usize, usize),
     pub direction: Direction,
@@ -33,13 +33,6 @@ impl SearchState {
         }
     }
     pub fn current_prefix(&self) -> Option<WordPosition> {
-        let start = self.position;
-        Board::get_pos_from_direction(
-            self.position.0,
-            self.position.1,
-            &self.direction,
-            self.distance,
-        )
-        .map(|end| WordPosition::new(start, end))
+        WordPosition::along(self.position, &self.direction, self.distance)
     }
 }
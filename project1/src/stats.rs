@@ -0,0 +1,135 @@
+use crate::board::Board;
+use crate::state::search_state::SearchState;
+use std::fmt;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+/// Counters describing how much work a search did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    pub states_visited: usize,
+    pub prefixes_checked: usize, // Lines of letters looked up in the trie
+    pub pruned_branches: usize,  // Lines that could go on but are not the prefix of any word
+    pub max_depth: usize,        // Length of the longest line looked up
+    pub words_found: usize,
+    pub elapsed: Duration, // Time spent searching
+}
+impl SearchStats {
+    /// Count one visited state, once `Board::check_state` has updated its feasibility
+    pub fn record(&mut self, board: &Board, state: &SearchState, found: bool) {
+        self.states_visited += 1;
+        let (i, j) = state.position;
+        let distance = state.distance;
+        if board
+            .get_string_from_direction(i, j, &state.direction, distance)
+            .is_none()
+        {
+            return;
+        }
+        self.prefixes_checked += 1;
+        self.max_depth = self.max_depth.max(distance as usize + 1);
+        // A line is only unfeasible while it can still grow when the trie rejected it
+        let can_grow = board
+            .get_string_from_direction(i, j, &state.direction, distance + 1)
+            .is_some();
+        if !state.feasible && can_grow {
+            self.pruned_branches += 1;
+        }
+        if found {
+            self.words_found += 1;
+        }
+    }
+    /// Share of the lines of an exhaustive search that were never looked up thanks to pruning.
+    /// `exhaustive` is the `exhaustive_prefixes` of the searched board, which can be kept per board
+    pub fn pruning_savings(&self, exhaustive: usize) -> f32 {
+        if exhaustive == 0 {
+            return 0.0;
+        }
+        1.0 - self.prefixes_checked as f32 / exhaustive as f32
+    }
+}
+impl fmt::Display for SearchStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "States: {}  Prefixes checked: {}  Pruned: {}\nMax depth: {}  Words: {}  Time: {:.2} ms",
+            self.states_visited,
+            self.prefixes_checked,
+            self.pruned_branches,
+            self.max_depth,
+            self.words_found,
+            self.elapsed.as_secs_f64() * 1000.0
+        )
+    }
+}
+
+/// Measures how long a search takes. `Instant` panics on wasm32-unknown-unknown, which has
+/// no clock, so nothing is measured there
+pub struct Stopwatch {
+    #[cfg(not(target_arch = "wasm32"))]
+    start: Instant,
+}
+impl Stopwatch {
+    pub fn start() -> Self {
+        Stopwatch {
+            #[cfg(not(target_arch = "wasm32"))]
+            start: Instant::now(),
+        }
+    }
+    pub fn elapsed(&self) -> Duration {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.start.elapsed();
+        #[cfg(target_arch = "wasm32")]
+        return Duration::ZERO;
+    }
+}
+
+/// Number of lines a search without pruning would look up: every length,
+/// in every direction, from every cell, the first letter once per direction like the search does
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::stats::exhaustive_prefixes;
+/// let board = Board::new(&vec![vec!['a', 'b']]);
+/// // "a" and "b" in the 8 directions, then "ab" and "ba"
+/// assert_eq!(exhaustive_prefixes(&board), 18);
+/// ```
+pub fn exhaustive_prefixes(board: &Board) -> usize {
+    let mut count = 0;
+    for i in 0..board.get_rows() {
+        for j in 0..board.get_cols() {
+            for direction in board.directions() {
+                let mut distance = 0;
+                while board
+                    .get_string_from_direction(i, j, direction, distance)
+                    .is_some()
+                {
+                    count += 1;
+                    distance += 1;
+                }
+            }
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{solve, solve_with_stats};
+    use crate::test_utils::{abc_board, trie_of};
+    #[test]
+    fn test_stats_of_a_search() {
+        let board = abc_board();
+        let (found, stats) = solve_with_stats(&board, &trie_of(&["abc", "ie"]));
+        assert_eq!(found, solve(&board, &trie_of(&["abc", "ie"])));
+        assert_eq!(stats.words_found, found.len());
+        assert_eq!(stats.words_found, 2);
+        assert_eq!(stats.max_depth, 3);
+        assert!(stats.prefixes_checked <= stats.states_visited);
+        assert!(stats.pruned_branches > 0);
+        let savings = stats.pruning_savings(exhaustive_prefixes(&board));
+        assert!(savings > 0.0 && savings < 1.0);
+    }
+}
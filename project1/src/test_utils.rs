@@ -0,0 +1,22 @@
+use crate::board::Board;
+use crate::trie::Trie;
+
+// A board from its rows of letters
+pub fn grid(rows: &[&str]) -> Board {
+    Board::new(&rows.iter().map(|row| row.chars().collect()).collect())
+}
+
+// The 3x3 board of the letters a to i, row by row
+pub fn abc_board() -> Board {
+    grid(&["abc", "def", "ghi"])
+}
+
+// Owned copies of the words
+pub fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
+
+// A trie of the words
+pub fn trie_of(words: &[&str]) -> Trie {
+    Trie::from(&words.to_vec())
+}
@@ -0,0 +1,248 @@
+use crate::export::Rgba;
+use ggez::graphics::Color;
+use ggez::{GameError, GameResult};
+use std::fs;
+use std::path::Path;
+
+/// Colors used to draw the visualizer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub background: Color,
+    pub grid: Color,             // Border of the grid
+    pub letters: Color,          // Letters of the board
+    pub text: Color,             // Word list and status line
+    pub probe: Color,            // Line being checked by the solver, or selected by the player
+    pub probe_cell: Color,       // Single cell being checked, and the hint
+    pub missing: Color,          // Words that could not be found
+    pub word_colors: Vec<Color>, // Given in turn to the found words
+}
+impl Theme {
+    /// Black on white, the original colors of the visualizer
+    pub fn light() -> Self {
+        Theme {
+            name: "light".to_string(),
+            background: Color::WHITE,
+            grid: Color::new(1.0, 0.0, 0.0, 1.0),
+            letters: Color::BLACK,
+            text: Color::BLACK,
+            probe: Color::new(0.0, 1.0, 0.0, 1.0),
+            probe_cell: Color::new(1.0, 0.0, 0.0, 0.5),
+            missing: Color::new(1.0, 0.0, 0.0, 1.0),
+            word_colors: vec![
+                Color::new(0.90, 0.45, 0.00, 1.0),
+                Color::new(0.00, 0.45, 0.70, 1.0),
+                Color::new(0.00, 0.62, 0.45, 1.0),
+                Color::new(0.80, 0.20, 0.50, 1.0),
+                Color::new(0.35, 0.60, 0.85, 1.0),
+                Color::new(0.55, 0.35, 0.20, 1.0),
+                Color::new(0.50, 0.30, 0.70, 1.0),
+                Color::new(0.75, 0.60, 0.00, 1.0),
+            ],
+        }
+    }
+    /// Light letters on a dark grey background
+    pub fn dark() -> Self {
+        Theme {
+            name: "dark".to_string(),
+            background: Color::new(0.12, 0.12, 0.14, 1.0),
+            grid: Color::new(0.45, 0.45, 0.50, 1.0),
+            letters: Color::new(0.92, 0.92, 0.92, 1.0),
+            text: Color::new(0.85, 0.85, 0.85, 1.0),
+            probe: Color::new(0.30, 0.80, 0.40, 1.0),
+            probe_cell: Color::new(0.95, 0.35, 0.35, 0.5),
+            missing: Color::new(1.0, 0.45, 0.45, 1.0),
+            word_colors: vec![
+                Color::new(1.00, 0.60, 0.20, 1.0),
+                Color::new(0.35, 0.70, 1.00, 1.0),
+                Color::new(0.30, 0.85, 0.60, 1.0),
+                Color::new(1.00, 0.45, 0.70, 1.0),
+                Color::new(0.75, 0.60, 1.00, 1.0),
+                Color::new(0.95, 0.85, 0.35, 1.0),
+            ],
+        }
+    }
+    /// Pure white on black with saturated strokes, for low vision
+    pub fn high_contrast() -> Self {
+        Theme {
+            name: "high contrast".to_string(),
+            background: Color::BLACK,
+            grid: Color::WHITE,
+            letters: Color::WHITE,
+            text: Color::WHITE,
+            probe: Color::new(1.0, 1.0, 0.0, 1.0),
+            probe_cell: Color::new(1.0, 1.0, 0.0, 0.6),
+            missing: Color::new(1.0, 0.4, 0.4, 1.0),
+            word_colors: vec![
+                Color::new(0.0, 1.0, 1.0, 1.0),
+                Color::new(1.0, 0.0, 1.0, 1.0),
+                Color::new(0.0, 1.0, 0.0, 1.0),
+                Color::new(1.0, 0.6, 0.0, 1.0),
+            ],
+        }
+    }
+    /// The Okabe-Ito palette, told apart with every common color vision deficiency
+    pub fn colorblind() -> Self {
+        Theme {
+            name: "colorblind safe".to_string(),
+            background: Color::WHITE,
+            grid: Color::BLACK,
+            letters: Color::BLACK,
+            text: Color::BLACK,
+            probe: Color::new(0.00, 0.45, 0.70, 1.0),
+            probe_cell: Color::new(0.90, 0.62, 0.00, 0.6),
+            missing: Color::new(0.84, 0.37, 0.00, 1.0),
+            word_colors: vec![
+                Color::new(0.90, 0.62, 0.00, 1.0),
+                Color::new(0.34, 0.71, 0.91, 1.0),
+                Color::new(0.00, 0.62, 0.45, 1.0),
+                Color::new(0.00, 0.45, 0.70, 1.0),
+                Color::new(0.84, 0.37, 0.00, 1.0),
+                Color::new(0.80, 0.47, 0.65, 1.0),
+            ],
+        }
+    }
+    /// Every built-in theme, in the order the theme key cycles through them
+    pub fn presets() -> Vec<Theme> {
+        vec![
+            Theme::light(),
+            Theme::dark(),
+            Theme::high_contrast(),
+            Theme::colorblind(),
+        ]
+    }
+    /// Color of the found word at `idx` in the word list
+    pub fn word_color(&self, idx: usize, alpha: f32) -> Color {
+        let color = self.word_colors[idx % self.word_colors.len()];
+        Color::new(color.r, color.g, color.b, alpha)
+    }
+    /// Read a theme from `key = value` lines, where values are colors as #RRGGBB or #RRGGBBAA.
+    /// `base` picks the preset the other keys change, so it must come before them,
+    /// `words` is a comma separated list of at least one color
+    /// # Example
+    /// ```
+    /// use word_search_solver::theme::Theme;
+    /// let theme = Theme::parse("base = dark\n# comment\nprobe = #FFFF00\nwords = #FF0000, #0000FF").unwrap();
+    /// assert_eq!(theme.background, Theme::dark().background);
+    /// assert_eq!(theme.probe, ggez::graphics::Color::new(1.0, 1.0, 0.0, 1.0));
+    /// assert_eq!(theme.word_colors.len(), 2);
+    /// assert!(Theme::parse("probe = green").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<Theme, String> {
+        let mut theme = Theme::light();
+        let mut name = None;
+        let mut changed_key = None; // First color set, that `base` would overwrite
+        for (line_idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected key = value", line_idx + 1))?;
+            let (key, value) = (key.trim(), value.trim());
+            let color = |value: &str| {
+                parse_color(value)
+                    .ok_or_else(|| format!("line {}: invalid color {}", line_idx + 1, value))
+            };
+            match key {
+                "name" => name = Some(value.to_string()),
+                "base" => {
+                    if let Some(changed_key) = changed_key {
+                        return Err(format!(
+                            "line {}: base must come before {}",
+                            line_idx + 1,
+                            changed_key
+                        ));
+                    }
+                    theme = Theme::presets()
+                        .into_iter()
+                        .find(|preset| preset.name == value)
+                        .ok_or_else(|| format!("line {}: unknown theme {}", line_idx + 1, value))?
+                }
+                "background" => theme.background = color(value)?,
+                "grid" => theme.grid = color(value)?,
+                "letters" => theme.letters = color(value)?,
+                "text" => theme.text = color(value)?,
+                "probe" => theme.probe = color(value)?,
+                "probe_cell" => theme.probe_cell = color(value)?,
+                "missing" => theme.missing = color(value)?,
+                "words" => {
+                    theme.word_colors = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|value| !value.is_empty())
+                        .map(color)
+                        .collect::<Result<_, _>>()?;
+                    if theme.word_colors.is_empty() {
+                        return Err(format!("line {}: no word colors", line_idx + 1));
+                    }
+                }
+                _ => return Err(format!("line {}: unknown key {}", line_idx + 1, key)),
+            }
+            if key != "name" && key != "base" {
+                changed_key.get_or_insert(key);
+            }
+        }
+        theme.name = name.unwrap_or_else(|| "custom".to_string());
+        Ok(theme)
+    }
+    /// Read a theme file, see `Theme::parse` for the format
+    pub fn load(path: &Path) -> GameResult<Theme> {
+        let text = fs::read_to_string(path).map_err(|e| {
+            GameError::ResourceLoadError(format!("Could not read {}: {}", path.display(), e))
+        })?;
+        Theme::parse(&text).map_err(|e| {
+            GameError::ResourceLoadError(format!("Invalid theme {}: {}", path.display(), e))
+        })
+    }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    let Rgba(r, g, b, a) = Rgba::from_hex(value)?;
+    Some(Color::from_rgba(r, g, b, a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_presets_have_distinct_names() {
+        let presets = Theme::presets();
+        for (i, theme) in presets.iter().enumerate() {
+            assert!(!theme.word_colors.is_empty());
+            assert!(presets[i + 1..]
+                .iter()
+                .all(|other| other.name != theme.name));
+        }
+    }
+    #[test]
+    fn test_parse_errors() {
+        assert!(Theme::parse("background #000000").is_err());
+        assert!(Theme::parse("base = sepia").is_err());
+        assert!(Theme::parse("border = #000000").is_err());
+        assert_eq!(
+            Theme::parse("words =").err(),
+            Some("line 1: no word colors".to_string())
+        );
+        assert_eq!(
+            Theme::parse("words = #FF0000, #00FF00,")
+                .unwrap()
+                .word_colors
+                .len(),
+            2
+        );
+        assert_eq!(
+            Theme::parse("name = mine\nprobe = #FFFF00\nbase = dark").err(),
+            Some("line 3: base must come before probe".to_string())
+        );
+        assert_eq!(Theme::parse("name = mine").unwrap().name, "mine");
+    }
+    #[test]
+    fn test_word_color_cycles() {
+        let theme = Theme::high_contrast();
+        let count = theme.word_colors.len();
+        assert_eq!(theme.word_color(0, 0.5), theme.word_color(count, 0.5));
+        assert_eq!(theme.word_color(1, 0.5).a, 0.5);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::board::{Board, Direction, WordPosition};
+use crate::state::search_state::SearchState;
+use crate::trie::Trie;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+
+/// One search state visited by the solver, written as one line of JSON
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    pub step: usize,
+    pub position: (usize, usize),
+    pub direction: Direction,
+    pub distance: i32,
+    pub feasible: bool, // Whether the line checked is still a prefix of a target word
+    pub found: Option<String>, // The word found at this state
+}
+impl TraceEntry {
+    /// The search state the entry was recorded from
+    pub fn search_state(&self) -> SearchState {
+        let mut state = SearchState::from(self.position, self.direction, self.distance);
+        state.feasible = self.feasible;
+        state
+    }
+    /// Position of the word found at this state
+    pub fn found_position(&self) -> Option<WordPosition> {
+        self.found.as_ref()?;
+        self.search_state().current_prefix()
+    }
+}
+
+/// Solve the board like `solver::solve`, recording every state visited
+/// # Example
+/// ```
+/// use word_search_solver::board::Board;
+/// use word_search_solver::trace::trace_search;
+/// use word_search_solver::trie::Trie;
+/// let board = Board::new(&vec![vec!['a', 'b'], vec!['c', 'd']]);
+/// let trace = trace_search(&board, &Trie::from(&vec!["ab"]));
+/// let found: Vec<_> = trace.iter().filter_map(|entry| entry.found.clone()).collect();
+/// assert_eq!(found, vec!["ab".to_string()]);
+/// assert_eq!(trace[0].step, 0);
+/// ```
+pub fn trace_search(board: &Board, trie: &Trie) -> Vec<TraceEntry> {
+    let mut state = SearchState::new();
+    let mut trace = Vec::new();
+    loop {
+        let (position, direction, distance) = (state.position, state.direction, state.distance);
+        let found = board
+            .check_state(&mut state, trie)
+            .map(|word_position| board.get_word(&word_position));
+        trace.push(TraceEntry {
+            step: trace.len(),
+            position,
+            direction,
+            distance,
+            feasible: state.feasible,
+            found,
+        });
+        match board.next_state(&state, state.feasible) {
+            Some(next_state) => state = next_state,
+            None => break,
+        }
+    }
+    trace
+}
+
+/// Write the trace as line-delimited JSON
+pub fn write_trace<W: Write>(trace: &[TraceEntry], mut writer: W) -> io::Result<()> {
+    for entry in trace {
+        serde_json::to_writer(&mut writer, entry)?;
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
+
+/// Read a trace written by `write_trace`, empty lines are skipped
+pub fn read_trace<R: BufRead>(reader: R) -> io::Result<Vec<TraceEntry>> {
+    let mut trace = Vec::new();
+    for (line_idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}", line_idx + 1, e),
+            )
+        })?;
+        trace.push(entry);
+    }
+    Ok(trace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::solve;
+    use crate::test_utils::{abc_board, trie_of};
+    #[test]
+    fn test_trace_matches_solve() {
+        let (board, trie) = (abc_board(), trie_of(&["abc", "ie", "hc"]));
+        let trace = trace_search(&board, &trie);
+        let found: Vec<WordPosition> = trace.iter().filter_map(|e| e.found_position()).collect();
+        assert_eq!(found, solve(&board, &trie));
+        assert!(trace.iter().any(|entry| !entry.feasible));
+    }
+    #[test]
+    fn test_write_and_read_trace() {
+        let (board, trie) = (abc_board(), trie_of(&["abc", "ie", "hc"]));
+        let trace = trace_search(&board, &trie);
+        let mut json = Vec::new();
+        write_trace(&trace, &mut json).unwrap();
+        let text = String::from_utf8(json.clone()).unwrap();
+        assert_eq!(text.lines().count(), trace.len());
+        assert!(
+            text.starts_with("{\"step\":0,\"position\":[0,0],\"direction\":\"Up\",\"distance\":0,")
+        );
+        assert_eq!(read_trace(&json[..]).unwrap(), trace);
+        let error = read_trace(&b"\n{\"step\":0}\n"[..]).unwrap_err();
+        assert!(error.to_string().starts_with("line 2:"));
+    }
+}
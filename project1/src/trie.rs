@@ -21,6 +21,15 @@ impl Default for TrieNode {
 pub struct Trie {
     root: TrieNode,
 }
+
+/// What a word may use of the letters given to `Trie::anagrams`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnagramOptions {
+    /// Only keep words that use every letter and every blank
+    pub use_all: bool,
+    /// Number of blanks, each one stands for any letter
+    pub blanks: usize,
+}
 impl Default for Trie {
     fn default() -> Self {
         Trie::new()
@@ -41,7 +50,7 @@ impl Trie {
         let mut current_node = &mut self.root;
 
         for c in word.chars() {
-            let next_node = current_node.children.entry(c).or_insert(TrieNode::new());
+            let next_node = current_node.children.entry(c).or_default();
             current_node = next_node;
         }
         current_node.is_word = true;
@@ -50,7 +59,7 @@ impl Trie {
         for word in words.to_owned().clone() {
             let mut current_node = &mut self.root;
             for c in word.chars() {
-                let next_node = current_node.children.entry(c).or_insert(TrieNode::new());
+                let next_node = current_node.children.entry(c).or_default();
                 current_node = next_node;
             }
             current_node.is_word = true;
@@ -82,6 +91,74 @@ impl Trie {
 
         true
     }
+    /// The words that can be written with the letters, each letter used at most as many
+    /// times as it is given. Longest words come first, then in alphabetical order
+    /// # Example
+    /// ```
+    /// use word_search_solver::trie::{AnagramOptions, Trie};
+    /// let trie = Trie::from(&vec!["TEA", "EAT", "ATE", "TEAS", "SEAT", "SET", "TO"]);
+    /// let words = trie.anagrams("SATE", AnagramOptions::default());
+    /// assert_eq!(words, vec!["SEAT", "TEAS", "ATE", "EAT", "SET", "TEA"]);
+    /// let all = AnagramOptions { use_all: true, ..Default::default() };
+    /// assert_eq!(trie.anagrams("ETA", all), vec!["ATE", "EAT", "TEA"]);
+    /// let blank = AnagramOptions { use_all: true, blanks: 1 };
+    /// assert_eq!(trie.anagrams("T", blank), vec!["TO"]);
+    /// ```
+    pub fn anagrams(&self, letters: &str, options: AnagramOptions) -> Vec<String> {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for c in letters.chars() {
+            *counts.entry(c).or_default() += 1;
+        }
+        let length = letters.chars().count() + options.blanks;
+        let mut words = Vec::new();
+        Trie::collect_anagrams(
+            &self.root,
+            &mut counts,
+            options.blanks,
+            &mut String::new(),
+            &mut words,
+        );
+        if options.use_all {
+            words.retain(|word| word.chars().count() == length);
+        }
+        words.sort_by(|a, b| {
+            b.chars()
+                .count()
+                .cmp(&a.chars().count())
+                .then_with(|| a.cmp(b))
+        });
+        words
+    }
+    // Walk the children that the remaining letters can spell. A given letter is used before a
+    // blank: the blank could stand for any later letter, so no word is missed
+    fn collect_anagrams(
+        node: &TrieNode,
+        counts: &mut HashMap<char, usize>,
+        blanks: usize,
+        word: &mut String,
+        words: &mut Vec<String>,
+    ) {
+        if node.is_word && !word.is_empty() {
+            words.push(word.clone());
+        }
+        for (&c, child) in &node.children {
+            let available = counts.get(&c).copied().unwrap_or(0);
+            let blanks_left = if available > 0 {
+                counts.insert(c, available - 1);
+                blanks
+            } else if blanks > 0 {
+                blanks - 1
+            } else {
+                continue;
+            };
+            word.push(c);
+            Trie::collect_anagrams(child, counts, blanks_left, word, words);
+            word.pop();
+            if available > 0 {
+                counts.insert(c, available);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -131,4 +208,24 @@ mod tests {
         assert!(trie.search("Five"));
         assert!(!trie.search("Six"));
     }
+    #[test]
+    fn test_anagrams_with_blanks() {
+        let trie = Trie::from(&vec!["BEE", "BE", "BEEF", "FEE", "EBB"]);
+        // Letters are not reused: one E is not enough for BEE
+        assert_eq!(trie.anagrams("BEF", AnagramOptions::default()), vec!["BE"]);
+        let one_blank = AnagramOptions {
+            use_all: false,
+            blanks: 1,
+        };
+        assert_eq!(
+            trie.anagrams("BEF", one_blank),
+            vec!["BEEF", "BEE", "EBB", "FEE", "BE"]
+        );
+        let all = AnagramOptions {
+            use_all: true,
+            blanks: 1,
+        };
+        assert_eq!(trie.anagrams("BEF", all), vec!["BEEF"]);
+        assert!(trie.anagrams("", AnagramOptions::default()).is_empty());
+    }
 }
@@ -1,38 +1,11 @@
-use crate::constant::*;
-use crate::state::main_state::MainState;
+use crate::board::{Board, WordPosition};
+use crate::heatmap::Heatmap;
+use crate::layout::Layout;
+use crate::solver::MissingWord;
+use crate::theme::Theme;
 use ggez::graphics::{self, Canvas, Color, Rect};
 use ggez::{glam::*, Context, GameResult};
-use std::fs;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-pub fn fetch_board(file_path: &Path) -> Vec<Vec<char>> {
-    let file = File::open(file_path).unwrap();
-    let reader = BufReader::new(file);
-    let mut result = Vec::new();
-    for line in reader.lines().flatten() {
-        let mut vec_letter = Vec::new();
-        for c in line.chars() {
-            if c.is_alphabetic() {
-                vec_letter.push(c);
-            }
-        }
-        result.push(vec_letter.clone());
-    }
-
-    println!("Letters: {:?}", result);
-    result
-}
-
-pub fn fetch_target_words(file_path: &Path) -> Vec<String> {
-    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let mut result = Vec::new();
-    for word in contents.split(' ') {
-        result.push(word.to_owned());
-    }
-    result
-}
-pub fn build_grid(ctx: &mut Context) -> graphics::Mesh {
+pub fn build_grid(ctx: &mut Context, layout: &Layout, theme: &Theme) -> graphics::Mesh {
     let mb = &mut graphics::MeshBuilder::new();
     // for i in 0..BOARD_SIZE {
     //     // Horizontal lines
@@ -62,15 +35,11 @@ pub fn build_grid(ctx: &mut Context) -> graphics::Mesh {
     //     )
     //     .unwrap();
     // }
+    let (grid_width, grid_height) = layout.grid_dimensions();
     mb.rectangle(
         graphics::DrawMode::stroke(1.0),
-        graphics::Rect::new(
-            START_X,
-            START_Y,
-            GRID_SIZE * BOARD_SIZE as f32,
-            GRID_SIZE * BOARD_SIZE as f32,
-        ),
-        graphics::Color::new(1.0, 0.0, 0.0, 1.0),
+        graphics::Rect::new(layout.start_x, layout.start_y, grid_width, grid_height),
+        theme.grid,
     )
     .unwrap();
     graphics::Mesh::from_data(ctx, mb.build())
@@ -78,130 +47,364 @@ pub fn build_grid(ctx: &mut Context) -> graphics::Mesh {
 // Draw temporary strike through that disappear in the next frame
 pub fn draw_highlighted_line(
     ctx: &mut Context,
-    start: Vec2,
-    end: Vec2,
+    word_position: &WordPosition,
     canvas: &mut Canvas,
     feasible: bool,
+    layout: &Layout,
+    theme: &Theme,
 ) {
     if !feasible {
         return;
     }
-    let start = Vec2::new(
-        START_X + GRID_SIZE * start.x + GRID_SIZE / 2.0,
-        START_Y + GRID_SIZE * start.y + GRID_SIZE / 2.0,
-    );
-    let end = Vec2::new(
-        START_X + GRID_SIZE * end.x + GRID_SIZE / 2.0,
-        START_Y + GRID_SIZE * end.y + GRID_SIZE / 2.0,
-    );
     let mb = &mut graphics::MeshBuilder::new();
-    if start == end {
-        mb.circle(
-            graphics::DrawMode::fill(),
-            start,
-            GRID_SIZE / 2.0,
-            1.0,
-            Color::new(1.0, 0.0, 0.0, 0.5),
-        )
-        .unwrap();
-    } else {
-        mb.line(&[start, end], 10.0, Color::new(0.0, 1.0, 0.0, 1.0))
+    // Follow the cells of the word, a line that skips cells marks each of its letters
+    for (start, end) in word_position.strokes() {
+        let start = Vec2::from(layout.cell_center(start.1 as f32, start.0 as f32));
+        let end = Vec2::from(layout.cell_center(end.1 as f32, end.0 as f32));
+        if start == end {
+            mb.circle(
+                graphics::DrawMode::fill(),
+                start,
+                layout.grid_size / 2.0,
+                1.0,
+                theme.probe_cell,
+            )
             .unwrap();
+        } else {
+            mb.line(&[start, end], layout.grid_size / 5.0, theme.probe)
+                .unwrap();
+        }
     }
     // Draw strike through
 
     let line_meshes = graphics::Mesh::from_data(ctx, mb.build());
     canvas.draw(&line_meshes, graphics::DrawParam::default());
 }
-pub fn draw_highlight(
+// Add the capsule around a found word to the strokes that stay on the screen
+pub fn add_stroke(
+    mb: &mut graphics::MeshBuilder,
+    layout: &Layout,
+    word_position: &WordPosition,
+    color: Color,
+) -> GameResult<()> {
+    for (start, end) in word_position.strokes() {
+        let start = Vec2::from(layout.cell_center(start.1 as f32, start.0 as f32));
+        let end = Vec2::from(layout.cell_center(end.1 as f32, end.0 as f32));
+        if start == end {
+            // A word that skips cells gets a dot on each of its letters
+            mb.circle(
+                graphics::DrawMode::fill(),
+                start,
+                layout.grid_size * 0.4,
+                0.5,
+                color,
+            )?;
+            continue;
+        }
+        // A stroke with round caps along the word is a capsule aligned with its direction
+        mb.polyline(
+            graphics::DrawMode::Stroke(
+                graphics::StrokeOptions::default()
+                    .with_line_width(layout.grid_size * 0.8)
+                    .with_line_cap(graphics::LineCap::Round),
+            ),
+            &[start, end],
+            color,
+        )?;
+    }
+    Ok(())
+}
+/// Fill every touched cell with `color`, more opaque the more it was touched
+pub fn draw_heatmap(
     ctx: &mut Context,
-    start: Vec2,
-    end: Vec2,
     canvas: &mut Canvas,
-    feasible: bool,
-) {
-    if !feasible {
-        return;
-    }
-    let start_x = START_X + GRID_SIZE * 0 as f32;
-    let start_y = START_Y + GRID_SIZE * 0 as f32;
-
-    let rect = Rect::new(start_x, start_y, GRID_SIZE * 15.0, GRID_SIZE * 1.0);
+    heatmap: &Heatmap,
+    layout: &Layout,
+    color: Color,
+) -> GameResult<()> {
     let mb = &mut graphics::MeshBuilder::new();
-    if start == end {
-        mb.circle(
+    let mut cells = 0;
+    let cols = heatmap.get_cols();
+    for (idx, intensity) in heatmap.intensities().into_iter().enumerate() {
+        if intensity == 0.0 {
+            continue;
+        }
+        let (i, j) = (idx / cols, idx % cols);
+        let (x, y) = layout.cell_center(j as f32, i as f32);
+        let half = layout.grid_size / 2.0;
+        mb.rectangle(
             graphics::DrawMode::fill(),
-            start,
-            GRID_SIZE / 2.0,
-            1.0,
-            Color::new(0.0, 0.0, 1.0, 1.0),
-        )
-        .unwrap();
-    } else {
-        mb.rounded_rectangle(graphics::DrawMode::fill(), rect, 15.0, Color::YELLOW)
-            .unwrap();
+            Rect::new(x - half, y - half, layout.grid_size, layout.grid_size),
+            Color::new(color.r, color.g, color.b, 0.1 + 0.7 * intensity),
+        )?;
+        cells += 1;
+    }
+    // An empty mesh cannot be drawn
+    if cells > 0 {
+        canvas.draw(
+            &graphics::Mesh::from_data(ctx, mb.build()),
+            graphics::DrawParam::new(),
+        );
+    }
+    Ok(())
+}
+/// Dim the cells used by found words and highlight the leftover ones, over the letters
+pub fn draw_leftovers(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    board: &Board,
+    leftover: &[(usize, usize)],
+    layout: &Layout,
+    theme: &Theme,
+) -> GameResult<()> {
+    let mb = &mut graphics::MeshBuilder::new();
+    let half = layout.grid_size / 2.0;
+    let cols = board.get_cols();
+    let mut is_leftover = vec![false; board.get_rows() * cols];
+    for &(i, j) in leftover {
+        is_leftover[i * cols + j] = true;
+    }
+    for i in 0..board.get_rows() {
+        for j in 0..cols {
+            let (x, y) = layout.cell_center(j as f32, i as f32);
+            let rect = Rect::new(x - half, y - half, layout.grid_size, layout.grid_size);
+            let color = if is_leftover[i * cols + j] {
+                Color::new(
+                    theme.probe_cell.r,
+                    theme.probe_cell.g,
+                    theme.probe_cell.b,
+                    0.3,
+                )
+            } else {
+                Color::new(
+                    theme.background.r,
+                    theme.background.g,
+                    theme.background.b,
+                    0.75,
+                )
+            };
+            mb.rectangle(graphics::DrawMode::fill(), rect, color)?;
+        }
     }
-    // Draw strike through
-
-    let line_meshes = graphics::Mesh::from_data(ctx, mb.build());
     canvas.draw(
-        &line_meshes,
-        graphics::DrawParam::default()
-            .offset([0.0, 0.0])
-            .rotation(1.0), // .rotation(1.0),
+        &graphics::Mesh::from_data(ctx, mb.build()),
+        graphics::DrawParam::new(),
     );
-} // Draw strike through that stay on the screen
-pub fn draw_line(
-    state: &mut MainState,
+    Ok(())
+}
+// Draw the grid and its letters once into an image the size of the window,
+// so a frame draws the whole board with a single draw call
+pub fn build_board_image(
+    ctx: &mut Context,
+    board: &Board,
+    grid_mesh: &graphics::Mesh,
+    layout: &Layout,
+    theme: &Theme,
+) -> GameResult<graphics::Image> {
+    // One texel per physical pixel keeps the letters sharp on HiDPI screens
+    let scale_factor = ctx.gfx.window().scale_factor() as f32;
+    let image = graphics::Image::new_canvas_image(
+        ctx,
+        ctx.gfx.surface_format(),
+        (layout.width * scale_factor).max(1.0) as u32,
+        (layout.height * scale_factor).max(1.0) as u32,
+        1,
+    );
+    let mut canvas = Canvas::from_image(ctx, image.clone(), Color::new(0.0, 0.0, 0.0, 0.0));
+    canvas.set_screen_coordinates(Rect::new(0.0, 0.0, layout.width, layout.height));
+    canvas.draw(grid_mesh, graphics::DrawParam::new());
+    for i in 0..board.get_rows() {
+        for j in 0..board.get_cols() {
+            canvas.draw(
+                graphics::Text::new(board.letters[i][j])
+                    .set_scale(layout.text_scale())
+                    .set_font("Montserrat"),
+                graphics::DrawParam::new()
+                    .dest(Vec2::from(layout.cell_center(j as f32, i as f32)))
+                    .color(theme.letters)
+                    .offset(Vec2::new(0.5, 0.5)),
+            );
+        }
+    }
+    canvas.finish(ctx)?;
+    Ok(image)
+}
+// display out answer as we find words, on the right of the grid
+// `scroll` moves the list up, entries outside of the panel are not drawn.
+// The visible entries are laid out as the lines of a single text, so the list is one draw
+pub fn display_word_list(
     ctx: &mut Context,
-    start_idx: usize,
-    end_idx: usize,
-    mb: &mut graphics::MeshBuilder,
     canvas: &mut Canvas,
+    entries: &[(String, Color)],
+    layout: &Layout,
+    scroll: f32,
 ) -> GameResult<()> {
-    let start = Vec2::new(
-        START_X + GRID_SIZE * (start_idx % BOARD_SIZE) as f32 + GRID_SIZE / 2.0,
-        START_Y + GRID_SIZE * (start_idx / BOARD_SIZE) as f32 + GRID_SIZE / 2.0,
+    let mut visible = (0..entries.len())
+        .filter_map(|idx| Some((idx, layout.word_entry_y(idx, scroll)?)))
+        .peekable();
+    let Some(&(first, y)) = visible.peek() else {
+        return Ok(());
+    };
+    let count = visible.count();
+    // A newline as tall as an entry spaces the lines like the separate entries were
+    let (ascent, line_advance) = font_metrics(ctx)?;
+    let spacer_scale = layout.line_height() / line_advance;
+    let mut text = graphics::Text::default();
+    for (word, color) in &entries[first..first + count] {
+        text.add(
+            graphics::TextFragment::new(word.as_str())
+                .scale(layout.text_scale())
+                .color(*color),
+        );
+        text.add(graphics::TextFragment::new("\n").scale(spacer_scale));
+    }
+    text.set_font("Montserrat").set_wrap(true);
+    // The taller newline also moves the first baseline down
+    let shift = ascent * (spacer_scale - layout.text_scale());
+    canvas.draw(
+        &text,
+        graphics::DrawParam::new().dest([layout.word_panel_x(), y - shift]),
     );
-    let end = Vec2::new(
-        START_X + GRID_SIZE * (end_idx % BOARD_SIZE) as f32 + GRID_SIZE / 2.0,
-        START_Y + GRID_SIZE * (end_idx / BOARD_SIZE) as f32 + GRID_SIZE / 2.0,
+    Ok(())
+}
+// Ascent and distance between two lines of the text font, for a scale of 1
+fn font_metrics(ctx: &Context) -> GameResult<(f32, f32)> {
+    const PROBE_SCALE: f32 = 100.0;
+    let mut probe = graphics::Text::new("A\nA");
+    probe
+        .set_font("Montserrat")
+        .set_scale(PROBE_SCALE)
+        .set_wrap(true);
+    let positions = probe.glyph_positions(ctx)?;
+    match (positions.first(), positions.last()) {
+        (Some(first), Some(last)) => Ok((first.y / PROBE_SCALE, (last.y - first.y) / PROBE_SCALE)),
+        _ => Ok((1.0, 1.0)),
+    }
+}
+// Draw a rounded background behind an entry of the word list
+pub fn highlight_word_entry(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    idx: usize,
+    layout: &Layout,
+    scroll: f32,
+    color: Color,
+) -> GameResult<()> {
+    let Some(y) = layout.word_entry_y(idx, scroll) else {
+        return Ok(());
+    };
+    let x = layout.word_panel_x();
+    let rect = Rect::new(
+        x - layout.grid_size * 0.2,
+        y - layout.grid_size * 0.15,
+        layout.width - x,
+        layout.line_height() * 0.9,
     );
-    mb.line(&[start, end], 5.0, Color::new(0.0, 0.0, 1.0, 1.0))?;
-    // Draw strike through
-    let line_mesh = graphics::Mesh::from_data(ctx, mb.build());
-    state.line_mesh = line_mesh;
-    canvas.draw(&state.line_mesh, graphics::DrawParam::new());
+    let mb = &mut graphics::MeshBuilder::new();
+    mb.rounded_rectangle(graphics::DrawMode::fill(), rect, rect.h / 2.0, color)?;
+    let mesh = graphics::Mesh::from_data(ctx, mb.build());
+    canvas.draw(&mesh, graphics::DrawParam::default());
     Ok(())
 }
-// display out answer as we find words
-pub fn display_word(_ctx: &mut Context, canvas: &mut Canvas, word: &str, idx: usize) {
-    let draw_params = graphics::DrawParam::new()
-        .dest([
-            START_X + GRID_SIZE * BOARD_SIZE as f32 + 100.0,
-            START_Y + 50.0 * idx as f32,
-        ])
-        .color(Color::new(0.0, 0.0, 0.0, 1.0));
+// display the words that could not be found, on the left of the grid, wrapped to the panel width.
+// The list starts at entry `first` and stops at the last entry that fits in the panel
+pub fn display_missing_words(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    entries: &[String],
+    first: usize,
+    layout: &Layout,
+    theme: &Theme,
+) -> GameResult<()> {
+    let bottom = layout.start_y + layout.panel_height();
+    let mut y = layout.start_y + 1.2 * layout.line_height();
+    let mut shown = 0;
+    for entry in entries.iter().skip(first) {
+        let mut text = graphics::Text::new(entry.as_str());
+        text.set_scale(layout.text_scale() * 0.6)
+            .set_bounds([layout.start_x - 40.0, f32::INFINITY])
+            .set_wrap(true)
+            .set_font("Montserrat");
+        let height = text.measure(ctx)?.y;
+        if y + height > bottom {
+            break;
+        }
+        canvas.draw(
+            &text,
+            graphics::DrawParam::new()
+                .dest([20.0, y])
+                .color(theme.missing),
+        );
+        y += height + 0.2 * layout.line_height();
+        shown += 1;
+    }
+    // Tell which entries are shown when the others are scrolled away
+    let header = if shown < entries.len() {
+        format!(
+            "Not found ({}-{} of {}):",
+            first + 1,
+            first + shown,
+            entries.len()
+        )
+    } else {
+        "Not found:".to_string()
+    };
     canvas.draw(
-        graphics::Text::new(word)
-            .set_scale(30.)
+        graphics::Text::new(header)
+            .set_scale(layout.text_scale() * 0.8)
             .set_font("Montserrat"),
-        draw_params,
+        graphics::DrawParam::new()
+            .dest([20.0, layout.start_y])
+            .color(theme.missing),
     );
+    Ok(())
 }
-#[cfg(test)]
-mod tests {
-    use std::path::Path;
-
-    use crate::utils::fetch_board;
-    #[test]
-    fn ensure_board_input_exists() {
-        let file_path = Path::new("src/input/board.txt");
-        fetch_board(file_path);
-    }
-    #[test]
-    fn ensure_target_words_input_exists() {
-        let file_path = Path::new("src/input/words.txt");
-        fetch_board(file_path);
+// display the playback status and key bindings under the grid
+pub fn display_status(
+    _ctx: &mut Context,
+    canvas: &mut Canvas,
+    status: &str,
+    layout: &Layout,
+    theme: &Theme,
+) {
+    canvas.draw(
+        graphics::Text::new(status)
+            .set_scale(20.)
+            .set_bounds([layout.width - 40.0, f32::INFINITY])
+            .set_font("Montserrat"),
+        graphics::DrawParam::new()
+            .dest([20.0, layout.status_y()])
+            .color(theme.text),
+    );
+}
+// display the search counters above the grid
+pub fn display_stats(
+    _ctx: &mut Context,
+    canvas: &mut Canvas,
+    stats: &str,
+    layout: &Layout,
+    theme: &Theme,
+) {
+    canvas.draw(
+        graphics::Text::new(stats)
+            .set_scale(16.)
+            .set_bounds([layout.width - 40.0, layout.start_y - 20.0])
+            .set_font("Montserrat"),
+        graphics::DrawParam::new()
+            .dest([20.0, 10.0])
+            .color(theme.text),
+    );
+}
+// Describe a missing word with its longest prefix and where the prefix starts and ends
+pub fn describe_missing_word(missing_word: &MissingWord) -> String {
+    match &missing_word.position {
+        Some(position) => format!(
+            "{} ({} at {:?}-{:?})",
+            missing_word.word,
+            missing_word.prefix,
+            position.start(),
+            position.end()
+        ),
+        None => format!("{} (no letter found)", missing_word.word),
     }
 }
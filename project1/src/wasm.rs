@@ -0,0 +1,65 @@
+use crate::api::handle;
+use wasm_bindgen::prelude::*;
+
+/// Solve a puzzle in the browser. Takes and returns the JSON bodies of `POST /solve`,
+/// an invalid request throws an `Error` whose message is the JSON of the API error
+#[wasm_bindgen(js_name = solve)]
+pub fn solve_json(request: &str) -> Result<String, JsError> {
+    call("/solve", request).map_err(|error| JsError::new(&error))
+}
+
+/// Generate a puzzle in the browser, with the JSON bodies of `POST /generate`
+#[wasm_bindgen(js_name = generate)]
+pub fn generate_json(request: &str) -> Result<String, JsError> {
+    call("/generate", request).map_err(|error| JsError::new(&error))
+}
+
+/// Check a puzzle in the browser, with the JSON bodies of `POST /validate`
+#[wasm_bindgen(js_name = validate)]
+pub fn validate_json(request: &str) -> Result<String, JsError> {
+    call("/validate", request).map_err(|error| JsError::new(&error))
+}
+
+// Answer like the HTTP API, the error is the JSON body of the failed answer.
+// Creating a `JsError` needs a JavaScript engine, so native tests stop here
+fn call(path: &str, request: &str) -> Result<String, String> {
+    let response = handle("POST", path, request.as_bytes());
+    if response.status == 200 {
+        Ok(response.body)
+    } else {
+        Err(response.body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_call() {
+        let body = call("/solve", r#"{"grid": ["DOG"], "words": ["god"]}"#).unwrap();
+        assert!(body.starts_with(r#"{"matches":[{"word":"GOD","start":[0,2],"end":[0,0]}]"#));
+        let error = call("/generate", r#"{"words": "rust"}"#).unwrap_err();
+        assert!(error.contains("invalid_json"));
+    }
+}
+
+// Run in a headless browser with `wasm-pack test --headless --firefox --no-default-features --features wasm`
+#[cfg(all(test, target_arch = "wasm32"))]
+mod wasm_tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn test_generate_then_solve() {
+        let generated = generate_json(r#"{"words": ["rust", "wasm"], "rows": 5, "cols": 5}"#)
+            .unwrap_or_else(|_| panic!("the puzzle fits"));
+        let generated: serde_json::Value = serde_json::from_str(&generated).unwrap();
+        let request = serde_json::json!({"grid": generated["grid"], "words": ["rust", "wasm"]});
+        let solved =
+            solve_json(&request.to_string()).unwrap_or_else(|_| panic!("the grid is valid"));
+        let solved: serde_json::Value = serde_json::from_str(&solved).unwrap();
+        assert_eq!(solved["missing"], serde_json::json!([]));
+        assert!(validate_json("{}").is_err());
+    }
+}